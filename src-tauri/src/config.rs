@@ -0,0 +1,914 @@
+use crate::farmer::parse_reward_address;
+use crate::logs::parse_log_directives;
+use crate::node::MAX_NODE_NAME_LENGTH;
+use crate::utils::{config_file_path, read_config, write_config};
+use sc_network::config::MultiaddrWithPeerId;
+use sc_network::Multiaddr;
+use sc_service::config::ExecutionStrategy;
+use sc_service::RpcMethods;
+use sc_telemetry::TelemetryEndpoints;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::num::NonZeroU16;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Key of the node settings inside the config file
+const NODE_CONFIG_KEY: &str = "node";
+
+/// Key of the performance settings inside the config file
+const PERFORMANCE_CONFIG_KEY: &str = "performance";
+
+/// Key of the log filter directives inside the config file
+pub(crate) const LOG_FILTER_KEY: &str = "logFilter";
+
+/// Key of the log retention settings inside the config file
+const LOG_RETENTION_KEY: &str = "logRetention";
+
+/// Port of the WS RPC server, the app and the farmer connect to it.
+const RPC_WS_PORT: u16 = 9947;
+
+/// Port of the WS RPC server of the executor node, when it is enabled.
+const EXECUTOR_RPC_WS_PORT: u16 = 9948;
+
+/// Port of the HTTP RPC server, when enabled.
+const RPC_HTTP_PORT: u16 = 9933;
+
+/// Origins of the app itself, always allowed to use the RPC.
+const APP_RPC_ORIGINS: &[&str] = &[
+    "tauri://localhost",
+    "https://tauri.localhost",
+    "http://localhost:3009",
+];
+
+/// The maximum number of runtime instances, same limit as the Substrate CLI.
+const MAX_RUNTIME_INSTANCES: usize = 256;
+
+/// Version of the config file layout, bumped with every new entry of `CONFIG_UPGRADES`.
+pub(crate) const CONFIG_SCHEMA_VERSION: u32 = 1;
+
+/// Key of the layout version inside the config file, configs without it are version 0.
+const SCHEMA_VERSION_KEY: &str = "schemaVersion";
+
+/// Upgrades of the config file layout, the entry at index `n` upgrades version `n` to `n + 1`.
+const CONFIG_UPGRADES: &[fn(&mut Map<String, Value>)] = &[numeric_plot_size];
+
+/// The whole config file, written by the frontend apart from the `node` and `performance` keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct AppConfig {
+    pub(crate) schema_version: u32,
+    pub(crate) plot: PlotConfig,
+    /// empty until the user went through the onboarding
+    pub(crate) reward_address: String,
+    pub(crate) launch_on_boot: bool,
+    /// version of the app that wrote the config
+    pub(crate) version: String,
+    pub(crate) node_name: String,
+    pub(crate) node: NodeConfig,
+    pub(crate) performance: PerformanceConfig,
+    /// log filter directives added to the default ones, for example `subspace_farmer=trace`
+    pub(crate) log_filter: String,
+    pub(crate) log_retention: LogRetentionConfig,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            schema_version: CONFIG_SCHEMA_VERSION,
+            plot: PlotConfig::default(),
+            reward_address: String::new(),
+            launch_on_boot: true,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            node_name: String::new(),
+            node: NodeConfig::default(),
+            performance: PerformanceConfig::default(),
+            log_filter: String::new(),
+            log_retention: LogRetentionConfig::default(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// validates the fields for which `changed` returns `true` when given their config key, the
+    /// values already in the config stay accepted, like a node name written before the rules
+    ///
+    /// empty values are accepted, they are filled in during the onboarding
+    pub(crate) fn validate(&self, changed: impl Fn(&str) -> bool) -> Result<(), String> {
+        if changed("plot") {
+            self.plot.validate()?;
+        }
+
+        if changed("rewardAddress") && !self.reward_address.is_empty() {
+            parse_reward_address(&self.reward_address)
+                .map_err(|why| format!("invalid reward address: {why}"))?;
+        }
+
+        if changed("nodeName") {
+            if self.node_name.chars().count() > MAX_NODE_NAME_LENGTH {
+                return Err(format!(
+                    "node name must be at most {MAX_NODE_NAME_LENGTH} characters"
+                ));
+            }
+            // same characters the node rejects in its name
+            if self.node_name.contains(['.', '@']) {
+                return Err("node name must not contain `.` or `@`".into());
+            }
+        }
+
+        if changed(LOG_FILTER_KEY) {
+            parse_log_directives(&self.log_filter)?;
+        }
+        if changed(LOG_RETENTION_KEY) {
+            self.log_retention.validate()?;
+        }
+        if changed(NODE_CONFIG_KEY) {
+            self.node.validate()?;
+        }
+        if changed(PERFORMANCE_CONFIG_KEY) {
+            self.performance.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct PlotConfig {
+    pub(crate) location: PathBuf,
+    /// the frontend may send it as a string
+    #[serde(rename = "sizeGB", deserialize_with = "deserialize_size_gb")]
+    pub(crate) size_gb: f64,
+}
+
+impl PlotConfig {
+    fn validate(&self) -> Result<(), String> {
+        if !self.location.as_os_str().is_empty() && !self.location.is_absolute() {
+            return Err("plot location must be an absolute path".into());
+        }
+        if !self.size_gb.is_finite() || self.size_gb < 0.0 {
+            return Err("plot size must be a positive number".into());
+        }
+        Ok(())
+    }
+}
+
+fn deserialize_size_gb<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SizeGb {
+        Number(f64),
+        String(String),
+    }
+
+    match SizeGb::deserialize(deserializer)? {
+        SizeGb::Number(size) => Ok(size),
+        SizeGb::String(size) => size.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+/// Node settings managed by the backend
+///
+/// Stored under the `node` key of the config file, next to the values written by the frontend.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct NodeConfig {
+    pub(crate) keystore: KeystoreKind,
+    pub(crate) peers: PeerConfig,
+    pub(crate) telemetry: TelemetryConfig,
+    pub(crate) execution: ExecutionConfig,
+    pub(crate) rpc: RpcConfig,
+    pub(crate) watchdog: WatchdogConfig,
+    pub(crate) executor: ExecutorConfig,
+}
+
+impl NodeConfig {
+    fn validate(&self) -> Result<(), String> {
+        self.peers.validate()?;
+        self.telemetry.validate()?;
+        self.execution.validate()?;
+        self.rpc.validate()?;
+        self.watchdog.validate()?;
+        if self.executor.enabled && self.keystore != KeystoreKind::Filesystem {
+            return Err(
+                "the executor needs the filesystem keystore to keep its executor key".into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Where the node keeps the keys inserted via RPC or the keystore commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum KeystoreKind {
+    /// keys are lost when the node stops
+    #[default]
+    InMemory,
+    /// keys are stored under the `keystore` directory of the network's config dir
+    Filesystem,
+}
+
+/// Peer settings of the node, useful for LAN setups and bandwidth-limited connections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct PeerConfig {
+    /// peers to always stay connected to, in the `/ip4/.../tcp/.../p2p/<peer id>` form
+    pub(crate) reserved_peers: Vec<String>,
+    /// when enabled, the node only connects to the reserved peers
+    pub(crate) reserved_only: bool,
+    /// boot nodes to use in addition to the ones from the chain spec
+    pub(crate) boot_nodes: Vec<String>,
+    /// external addresses of the node, advertised to the other peers
+    pub(crate) public_addresses: Vec<String>,
+    /// maximum number of inbound peers
+    pub(crate) in_peers: u32,
+    /// number of outbound peers to maintain
+    pub(crate) out_peers: u32,
+}
+
+impl Default for PeerConfig {
+    fn default() -> Self {
+        Self {
+            reserved_peers: vec![],
+            reserved_only: false,
+            boot_nodes: vec![],
+            public_addresses: vec![],
+            // Full + Light clients
+            in_peers: 25 + 100,
+            // Increase default value of 25 to improve success rate of sync
+            out_peers: 50,
+        }
+    }
+}
+
+impl PeerConfig {
+    pub(crate) fn reserved_peers(&self) -> Result<Vec<MultiaddrWithPeerId>, String> {
+        parse_addresses(&self.reserved_peers, "reserved peer")
+    }
+
+    pub(crate) fn boot_nodes(&self) -> Result<Vec<MultiaddrWithPeerId>, String> {
+        parse_addresses(&self.boot_nodes, "boot node")
+    }
+
+    pub(crate) fn public_addresses(&self) -> Result<Vec<Multiaddr>, String> {
+        parse_addresses(&self.public_addresses, "public address")
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.reserved_peers()?;
+        self.boot_nodes()?;
+        self.public_addresses()?;
+
+        if self.reserved_only && self.reserved_peers.is_empty() {
+            return Err("reserved-only mode requires at least one reserved peer".into());
+        }
+        if self.out_peers == 0 && !self.reserved_only {
+            return Err("number of outbound peers must be greater than 0".into());
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_addresses<T>(addresses: &[String], kind: &str) -> Result<Vec<T>, String>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    addresses
+        .iter()
+        .map(|address| {
+            address
+                .trim()
+                .parse()
+                .map_err(|why| format!("invalid {kind} `{address}`: {why}"))
+        })
+        .collect()
+}
+
+/// Where the node sends its telemetry, the node name is part of the telemetry data
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub(crate) enum TelemetryConfig {
+    /// telemetry is disabled
+    Off,
+    /// endpoints from the chain spec
+    #[default]
+    ChainDefault,
+    /// user provided endpoints, for example a self-hosted telemetry server
+    Custom { endpoints: Vec<TelemetryEndpoint> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TelemetryEndpoint {
+    /// for example `wss://telemetry.example.com/submit/`
+    pub(crate) url: String,
+    /// between 0 (least verbose) and 9 (most verbose)
+    pub(crate) verbosity: u8,
+}
+
+/// The maximum verbosity level accepted for a telemetry endpoint, same as the Substrate CLI.
+const MAX_TELEMETRY_VERBOSITY: u8 = 9;
+
+impl TelemetryConfig {
+    /// resolves the endpoints the node should use, given the endpoints of the chain spec
+    pub(crate) fn endpoints(
+        &self,
+        chain_default: Option<TelemetryEndpoints>,
+    ) -> Result<Option<TelemetryEndpoints>, String> {
+        match self {
+            Self::Off => Ok(None),
+            Self::ChainDefault => Ok(chain_default),
+            Self::Custom { endpoints } => {
+                let endpoints = endpoints
+                    .iter()
+                    .map(|endpoint| (endpoint.url.trim().to_string(), endpoint.verbosity))
+                    .collect();
+                TelemetryEndpoints::new(endpoints)
+                    .map(Some)
+                    .map_err(|why| format!("invalid telemetry endpoint: {why}"))
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Self::Custom { endpoints } = self {
+            if endpoints.is_empty() {
+                return Err("at least one custom telemetry endpoint is required".into());
+            }
+            for endpoint in endpoints {
+                if endpoint.verbosity > MAX_TELEMETRY_VERBOSITY {
+                    return Err(format!(
+                        "verbosity of `{}` must be between 0 and {MAX_TELEMETRY_VERBOSITY}",
+                        endpoint.url
+                    ));
+                }
+            }
+        }
+        self.endpoints(None).map(|_| ())
+    }
+}
+
+/// How the node executes the runtime
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct ExecutionConfig {
+    pub(crate) strategy: ExecutionKind,
+    /// directory with runtime wasm blobs replacing the on-chain ones with the same version,
+    /// useful for testing runtime upgrades locally
+    pub(crate) runtime_overrides: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ExecutionKind {
+    /// always execute the on-chain wasm runtime
+    #[default]
+    Wasm,
+    /// execute the runtime compiled into the app when its version matches the on-chain one
+    Native,
+    /// execute the native runtime, fall back to wasm if it fails
+    NativeElseWasm,
+}
+
+impl ExecutionConfig {
+    pub(crate) fn strategy(&self) -> ExecutionStrategy {
+        match self.strategy {
+            ExecutionKind::Wasm => ExecutionStrategy::AlwaysWasm,
+            ExecutionKind::Native => ExecutionStrategy::NativeWhenPossible,
+            ExecutionKind::NativeElseWasm => ExecutionStrategy::NativeElseWasm,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match &self.runtime_overrides {
+            Some(path) if !path.is_dir() => Err(format!(
+                "runtime overrides directory does not exist: {}",
+                path.display()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// RPC servers of the node, for example to point polkadot.js at the desktop node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct RpcConfig {
+    /// interface of the HTTP RPC server, which is disabled when `None`
+    pub(crate) http_interface: Option<IpAddr>,
+    /// interface of the WS RPC server, 127.0.0.1 or all interfaces, the app and the farmer
+    /// connect to it through 127.0.0.1
+    pub(crate) ws_interface: IpAddr,
+    pub(crate) methods: RpcMethodsKind,
+    /// origins allowed to use the RPC, in addition to the app itself
+    pub(crate) cors: Vec<String>,
+    /// maximum number of WS connections, Substrate's default is used when `None`
+    pub(crate) max_connections: Option<usize>,
+    /// maximum payload of requests and responses in MiB
+    pub(crate) max_payload: Option<usize>,
+    /// maximum size of requests in MiB
+    pub(crate) max_request_size: Option<usize>,
+    /// maximum size of responses in MiB
+    pub(crate) max_response_size: Option<usize>,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            http_interface: None,
+            ws_interface: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            methods: RpcMethodsKind::default(),
+            // Below CORS are default from Substrate
+            cors: vec![
+                "http://localhost:*".to_string(),
+                "http://127.0.0.1:*".to_string(),
+                "https://localhost:*".to_string(),
+                "https://127.0.0.1:*".to_string(),
+                "https://polkadot.js.org".to_string(),
+            ],
+            max_connections: None,
+            max_payload: None,
+            max_request_size: None,
+            max_response_size: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum RpcMethodsKind {
+    /// unsafe methods are only allowed when the RPC is bound to localhost
+    /// the app needs the unsafe `system_peers` method to show the peers during sync
+    #[default]
+    Auto,
+    Safe,
+    Unsafe,
+}
+
+impl RpcConfig {
+    pub(crate) fn http_address(&self) -> Option<SocketAddr> {
+        self.http_interface
+            .map(|interface| SocketAddr::new(interface, RPC_HTTP_PORT))
+    }
+
+    pub(crate) fn ws_address(&self) -> SocketAddr {
+        SocketAddr::new(self.ws_interface, RPC_WS_PORT)
+    }
+
+    pub(crate) fn executor_ws_address(&self) -> SocketAddr {
+        SocketAddr::new(self.ws_interface, EXECUTOR_RPC_WS_PORT)
+    }
+
+    pub(crate) fn methods(&self) -> RpcMethods {
+        match self.methods {
+            RpcMethodsKind::Auto => RpcMethods::Auto,
+            RpcMethodsKind::Safe => RpcMethods::Safe,
+            RpcMethodsKind::Unsafe => RpcMethods::Unsafe,
+        }
+    }
+
+    pub(crate) fn cors(&self) -> Vec<String> {
+        APP_RPC_ORIGINS
+            .iter()
+            .map(|origin| origin.to_string())
+            .chain(self.cors.iter().cloned())
+            .collect()
+    }
+
+    /// warnings to show to the user about the RPC being reachable from other machines, or not
+    /// allowing the unsafe methods the app uses
+    pub(crate) fn warnings(&self) -> Vec<String> {
+        let mut warnings = vec![];
+        for (name, interface) in [
+            ("HTTP", self.http_interface),
+            ("WS", Some(self.ws_interface)),
+        ] {
+            if let Some(interface) = interface.filter(|interface| !interface.is_loopback()) {
+                warnings.push(format!(
+                    "{name} RPC is bound to {interface}, other machines on the network can \
+                    connect to your node"
+                ));
+                if self.methods == RpcMethodsKind::Unsafe {
+                    warnings.push(format!(
+                        "unsafe RPC methods are exposed on {interface}, anyone who can reach it \
+                        can control your node"
+                    ));
+                }
+            }
+        }
+
+        // with `Auto`, Substrate decides by the interface the WS server is bound to, not by the
+        // address of the client, so the app connecting through 127.0.0.1 is denied them too
+        let unsafe_denied = match self.methods {
+            RpcMethodsKind::Auto => !self.ws_interface.is_loopback(),
+            RpcMethodsKind::Safe => true,
+            RpcMethodsKind::Unsafe => false,
+        };
+        if unsafe_denied {
+            warnings.push(
+                "unsafe RPC methods are disabled, the app can not show the number of peers \
+                (`system_peers`) while syncing, set the RPC methods to unsafe to show it"
+                    .into(),
+            );
+        }
+        warnings
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        // `::1` and the rest of `127.0.0.0/8` are loopbacks too, but the app and the farmer would
+        // not reach the node there
+        if self.ws_interface != IpAddr::V4(Ipv4Addr::LOCALHOST)
+            && !self.ws_interface.is_unspecified()
+        {
+            return Err(format!(
+                "WS RPC must listen on 127.0.0.1 or on all interfaces (0.0.0.0 or ::), the app \
+                and the farmer connect to it through 127.0.0.1:{RPC_WS_PORT}"
+            ));
+        }
+        for size in [
+            self.max_payload,
+            self.max_request_size,
+            self.max_response_size,
+            self.max_connections,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if size == 0 {
+                return Err("RPC limits must be greater than 0".into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Executor node of the execution chain, started next to the node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct ExecutorConfig {
+    /// the executor keeps its own database under the `executor` directory of the base directory,
+    /// and its WS RPC listens on the same interface as the node's, on port 9948
+    pub(crate) enabled: bool,
+}
+
+/// Restarts the node when it exits on its own or stops syncing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct WatchdogConfig {
+    pub(crate) enabled: bool,
+    /// the node is restarted when there is no new best block for this many minutes,
+    /// while it has peers connected
+    pub(crate) sync_stall_minutes: u64,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sync_stall_minutes: 10,
+        }
+    }
+}
+
+impl WatchdogConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.sync_stall_minutes == 0 {
+            return Err("sync stall timeout must be at least 1 minute".into());
+        }
+        Ok(())
+    }
+}
+
+/// How long and how much of the logs are kept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct LogRetentionConfig {
+    /// log files last written before this many days are removed
+    pub(crate) max_age_days: u64,
+    /// the oldest log files are removed while all of them together are larger than this
+    pub(crate) max_total_size_mib: u64,
+    /// whether the files of the previous hours are gzip-compressed
+    pub(crate) compress: bool,
+}
+
+impl Default for LogRetentionConfig {
+    fn default() -> Self {
+        Self {
+            max_age_days: 7,
+            max_total_size_mib: 1024,
+            compress: true,
+        }
+    }
+}
+
+impl LogRetentionConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.max_age_days == 0 {
+            return Err("logs must be kept for at least 1 day".into());
+        }
+        if self.max_total_size_mib < 10 {
+            return Err("logs must be allowed to take at least 10 MiB".into());
+        }
+        Ok(())
+    }
+}
+
+/// Memory and disk usage of the node and the farmer, either a named profile or custom values
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "profile", rename_all = "camelCase")]
+pub(crate) enum PerformanceConfig {
+    /// for machines with 8 GiB of memory or less
+    LowMemory,
+    #[default]
+    Default,
+    /// for machines with plenty of memory and fast disks
+    Performance,
+    Custom(PerformanceSettings),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PerformanceSettings {
+    /// size of the node's state cache in bytes
+    pub(crate) state_cache_size: usize,
+    /// maximum number of runtime instances the node keeps around
+    pub(crate) max_runtime_instances: usize,
+    /// maximum number of different runtimes that can be cached
+    pub(crate) runtime_cache_size: u8,
+    /// number of plots the farmer processes concurrently on a disk
+    pub(crate) disk_concurrency: NonZeroU16,
+}
+
+impl PerformanceConfig {
+    pub(crate) fn settings(&self) -> PerformanceSettings {
+        let (state_cache_size, max_runtime_instances, runtime_cache_size, disk_concurrency) =
+            match self {
+                Self::LowMemory => (16 * 1024 * 1024, 2, 1, 1),
+                Self::Default => (64 * 1024 * 1024, 8, 2, 2),
+                Self::Performance => (256 * 1024 * 1024, 16, 4, 4),
+                Self::Custom(settings) => return *settings,
+            };
+
+        PerformanceSettings {
+            state_cache_size,
+            max_runtime_instances,
+            runtime_cache_size,
+            disk_concurrency: NonZeroU16::new(disk_concurrency)
+                .expect("hard-coded value is correct"),
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let settings = self.settings();
+        if settings.max_runtime_instances == 0
+            || settings.max_runtime_instances > MAX_RUNTIME_INSTANCES
+        {
+            return Err(format!(
+                "maximum runtime instances must be between 1 and {MAX_RUNTIME_INSTANCES}"
+            ));
+        }
+        if settings.runtime_cache_size == 0 {
+            return Err("runtime cache size must be greater than 0".into());
+        }
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub(crate) fn get_config(app_handle: tauri::AppHandle) -> Result<AppConfig, String> {
+    let content = read_config(app_handle)?;
+    parse_config(&content)
+}
+
+/// replaces the keys present in `config`, the sections it leaves out, like `node` or
+/// `performance`, are kept as they are in the config file, `write_config` validates the result
+#[tauri::command]
+pub(crate) fn update_config(
+    config: Map<String, Value>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let mut merged = read_config_object(&app_handle)?;
+    merged.extend(config);
+
+    let content = serde_json::to_string_pretty(&merged)
+        .map_err(|why| format!("couldn't serialize the config because: {why}"))?;
+    write_config(content, app_handle)
+}
+
+/// parses the content of the config file, without validating it
+fn parse_config(content: &str) -> Result<AppConfig, String> {
+    serde_json::from_str(content).map_err(|why| format!("couldn't parse the config because: {why}"))
+}
+
+/// validates a config about to replace the `previous` one, keeping the keys `AppConfig` does not
+/// know about, only the fields that differ from the previous config are validated
+///
+/// configs without a layout version come from the frontend of this app version and are stamped
+/// with the current one
+pub(crate) fn prepare_config(content: &str, previous: Option<&str>) -> Result<String, String> {
+    let mut config = match serde_json::from_str(content) {
+        Ok(Value::Object(config)) => config,
+        Ok(_) => return Err("config does not contain a JSON object".to_string()),
+        Err(why) => return Err(format!("couldn't parse the config because: {why}")),
+    };
+    config
+        .entry(SCHEMA_VERSION_KEY)
+        .or_insert_with(|| CONFIG_SCHEMA_VERSION.into());
+
+    // an unreadable previous config counts as missing, everything is validated then
+    let previous = previous
+        .and_then(|previous| serde_json::from_str::<Map<String, Value>>(previous).ok())
+        .unwrap_or_default();
+    let value = Value::Object(config);
+    AppConfig::deserialize(&value)
+        .map_err(|why| format!("couldn't parse the config because: {why}"))?
+        .validate(|key| previous.get(key) != value.get(key))?;
+
+    serde_json::to_string_pretty(&value)
+        .map_err(|why| format!("couldn't serialize the config because: {why}"))
+}
+
+/// brings the config file written by a previous app version up to the current layout, one
+/// version at a time, and records the current app version in it
+pub(crate) fn upgrade_config(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    // nothing to upgrade before the onboarding
+    if !config_file_path(app_handle.clone()).exists() {
+        return Ok(());
+    }
+
+    let mut config = read_config_object(app_handle)?;
+    if schema_version(&config)? == CONFIG_SCHEMA_VERSION
+        && config.get("version").and_then(Value::as_str) == Some(env!("CARGO_PKG_VERSION"))
+    {
+        return Ok(());
+    }
+
+    upgrade_config_object(&mut config)?;
+    let content = serde_json::to_string_pretty(&config)
+        .map_err(|why| format!("couldn't serialize the config because: {why}"))?;
+    write_config(content, app_handle.clone())
+}
+
+/// upgrades a config written by this or a previous app version to the current layout
+pub(crate) fn upgrade_config_object(config: &mut Map<String, Value>) -> Result<(), String> {
+    let schema_version = schema_version(config)?;
+    if schema_version > CONFIG_SCHEMA_VERSION {
+        return Err(format!(
+            "config version {schema_version} was written by a newer version of the app"
+        ));
+    }
+
+    for (version, upgrade) in CONFIG_UPGRADES
+        .iter()
+        .enumerate()
+        .skip(schema_version as usize)
+    {
+        info!(
+            "Upgrading the config from version {version} to {}",
+            version + 1
+        );
+        upgrade(config);
+    }
+    config.insert(SCHEMA_VERSION_KEY.to_string(), CONFIG_SCHEMA_VERSION.into());
+    config.insert("version".to_string(), env!("CARGO_PKG_VERSION").into());
+
+    Ok(())
+}
+
+fn schema_version(config: &Map<String, Value>) -> Result<u32, String> {
+    match config.get(SCHEMA_VERSION_KEY) {
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| format!("invalid `{SCHEMA_VERSION_KEY}` in the config")),
+        None => Ok(0),
+    }
+}
+
+/// version 0 to 1: the plot size was stored as a string by older versions of the frontend
+fn numeric_plot_size(config: &mut Map<String, Value>) {
+    let size_gb = config
+        .get_mut("plot")
+        .and_then(Value::as_object_mut)
+        .and_then(|plot| plot.get_mut("sizeGB"));
+
+    if let Some(size_gb) = size_gb {
+        if let Some(size) = size_gb
+            .as_str()
+            .and_then(|size| size.trim().parse::<f64>().ok())
+        {
+            if let Some(number) = serde_json::Number::from_f64(size) {
+                *size_gb = Value::Number(number);
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) fn get_node_config(app_handle: tauri::AppHandle) -> Result<NodeConfig, String> {
+    read_section(&app_handle, NODE_CONFIG_KEY)
+}
+
+/// the new settings are applied on the next `start_node`
+/// returns the warnings to show to the user about the new settings
+#[tauri::command]
+pub(crate) fn update_node_config(
+    node_config: NodeConfig,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<String>, String> {
+    node_config.validate()?;
+    write_section(&app_handle, NODE_CONFIG_KEY, &node_config)?;
+    Ok(node_config.rpc.warnings())
+}
+
+#[tauri::command]
+pub(crate) fn get_performance_config(
+    app_handle: tauri::AppHandle,
+) -> Result<PerformanceConfig, String> {
+    read_section(&app_handle, PERFORMANCE_CONFIG_KEY)
+}
+
+/// the new settings are applied on the next `start_node` and `farming`
+#[tauri::command]
+pub(crate) fn update_performance_config(
+    performance_config: PerformanceConfig,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    performance_config.validate()?;
+    write_section(&app_handle, PERFORMANCE_CONFIG_KEY, &performance_config)
+}
+
+#[tauri::command]
+pub(crate) fn get_log_retention_config(
+    app_handle: tauri::AppHandle,
+) -> Result<LogRetentionConfig, String> {
+    read_section(&app_handle, LOG_RETENTION_KEY)
+}
+
+/// the new settings are applied on the next log cleanup, within the hour
+#[tauri::command]
+pub(crate) fn update_log_retention_config(
+    log_retention_config: LogRetentionConfig,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    log_retention_config.validate()?;
+    write_section(&app_handle, LOG_RETENTION_KEY, &log_retention_config)
+}
+
+/// reads a section of the config file, falls back to the default value if the section is missing
+pub(crate) fn read_section<T: DeserializeOwned + Default>(
+    app_handle: &tauri::AppHandle,
+    key: &str,
+) -> Result<T, String> {
+    match read_config_object(app_handle)?.remove(key) {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|why| format!("couldn't parse `{key}` from the config because: {why}")),
+        None => Ok(T::default()),
+    }
+}
+
+/// replaces a section of the config file, leaving the rest of the config untouched
+///
+/// the config must exist, a config with only this section would make the frontend skip the
+/// onboarding and fail on the missing plot settings
+pub(crate) fn write_section<T: Serialize>(
+    app_handle: &tauri::AppHandle,
+    key: &str,
+    value: &T,
+) -> Result<(), String> {
+    if !config_file_path(app_handle.clone()).exists() {
+        return Err("there is no config yet, finish the onboarding first".to_string());
+    }
+
+    let mut config = read_config_object(app_handle)?;
+    let value = serde_json::to_value(value)
+        .map_err(|why| format!("couldn't serialize `{key}` because: {why}"))?;
+    config.insert(key.to_string(), value);
+
+    let content = serde_json::to_string_pretty(&config)
+        .map_err(|why| format!("couldn't serialize the config because: {why}"))?;
+    write_config(content, app_handle.clone())
+}
+
+fn read_config_object(app_handle: &tauri::AppHandle) -> Result<Map<String, Value>, String> {
+    let path = config_file_path(app_handle.clone());
+
+    // config is not created yet by the frontend, the sections have their default values
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+
+    let content =
+        fs::read_to_string(path).map_err(|why| format!("couldn't read file because: {why}"))?;
+    match serde_json::from_str(&content) {
+        Ok(Value::Object(config)) => Ok(config),
+        Ok(_) => Err("config file does not contain a JSON object".to_string()),
+        Err(why) => Err(format!("couldn't parse the config because: {why}")),
+    }
+}
@@ -0,0 +1,210 @@
+use crate::farmer::farming_started;
+use crate::logs::log_files;
+use crate::node::node_running;
+use crate::utils::{custom_log_dir, write_atomically};
+use crate::watchdog::try_get_node_incidents;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::backtrace::Backtrace;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::panic::{self, PanicInfo};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::info;
+
+/// Name of the crash report in the log directory, it stays there until the user dismisses it.
+const CRASH_REPORT_FILE: &str = "crash-report.json";
+
+/// Number of log lines added to the crash report, the newest ones.
+const CRASH_REPORT_LOG_LINES: usize = 200;
+
+/// Maximum size read from the end of the log file to find the recent log lines.
+const CRASH_REPORT_LOG_BYTES: u64 = 256 * 1024;
+
+/// Log directory and start time of the app, set once by `init`.
+static CRASH_CONTEXT: Mutex<Option<(PathBuf, Instant)>> = Mutex::new(None);
+
+/// Report written when the app panics, offered to the user on the next launch
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CrashReport {
+    /// RFC 3339 timestamp
+    time: String,
+    app_version: String,
+    os: String,
+    arch: String,
+    thread: String,
+    message: String,
+    /// `file:line:column` of the panic
+    location: Option<String>,
+    backtrace: String,
+    /// newest bunyan log lines before the panic, oldest first
+    recent_logs: Vec<String>,
+    /// uptime, node and farmer state at the time of the panic
+    state: Value,
+}
+
+/// returns the crash report left by a previous panic, if the user did not dismiss it yet
+#[tauri::command]
+pub(crate) fn get_pending_crash_report(
+    app_handle: tauri::AppHandle,
+) -> Result<Option<CrashReport>, String> {
+    let path = crash_report_path(&app_handle);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content =
+        fs::read(&path).map_err(|why| format!("couldn't read the crash report because: {why}"))?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|why| format!("couldn't parse the crash report because: {why}"))
+}
+
+/// copies the pending crash report into the `destination` file
+#[tauri::command]
+pub(crate) fn save_crash_report(
+    destination: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    fs::copy(crash_report_path(&app_handle), &destination)
+        .map_err(|why| format!("couldn't save the crash report because: {why}"))?;
+
+    info!("Saved the crash report into {destination}");
+    Ok(())
+}
+
+/// removes the pending crash report, it is not offered again
+#[tauri::command]
+pub(crate) fn dismiss_crash_report(app_handle: tauri::AppHandle) -> Result<(), String> {
+    match fs::remove_file(crash_report_path(&app_handle)) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(why) => Err(format!("couldn't dismiss the crash report because: {why}")),
+    }
+}
+
+fn crash_report_path(app_handle: &tauri::AppHandle) -> PathBuf {
+    custom_log_dir(&app_handle.config().tauri.bundle.identifier).join(CRASH_REPORT_FILE)
+}
+
+/// keeps the log directory for the crash reports and installs the panic hook, to call once the
+/// logger is set up
+pub(crate) fn init(log_dir: PathBuf) {
+    if log_dir.join(CRASH_REPORT_FILE).exists() {
+        info!("Found the crash report of a previous run");
+    }
+
+    *CRASH_CONTEXT
+        .lock()
+        .expect("crash context lock is never poisoned") = Some((log_dir, Instant::now()));
+    set_panic_hook();
+}
+
+/// writes a crash report, then calls the hook set before, which prints the panic to stderr
+///
+/// a panic caught in a task does not end the app, the report of a later panic replaces its report
+/// so the one that ended the app is offered
+///
+/// `sp_panic_handler::set` replaces the hook and exits the process from its own, this must be
+/// called again after it
+pub(crate) fn set_panic_hook() {
+    let (log_dir, started) = match CRASH_CONTEXT
+        .lock()
+        .expect("crash context lock is never poisoned")
+        .clone()
+    {
+        Some(context) => context,
+        None => return,
+    };
+
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        // no tracing in here, the panic may come from the logger while it holds its locks
+        let report = crash_report(panic_info, &log_dir, started);
+        let content = serde_json::to_vec_pretty(&report).unwrap_or_default();
+        if let Err(error) = write_atomically(&log_dir.join(CRASH_REPORT_FILE), &content) {
+            eprintln!("Failed to write the crash report: {error}");
+        }
+
+        previous_hook(panic_info);
+    }));
+}
+
+fn crash_report(panic_info: &PanicInfo<'_>, log_dir: &Path, started: Instant) -> CrashReport {
+    let payload = panic_info.payload();
+    let message = match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "Box<dyn Any>".to_string(),
+        },
+    };
+
+    // the state is read without waiting for the locks, the panicking thread may hold them
+    let state = json!({
+        "uptimeSecs": started.elapsed().as_secs(),
+        "nodeRunning": node_running(),
+        "farmingStarted": farming_started(),
+        "incidents": try_get_node_incidents(),
+    });
+
+    CrashReport {
+        time: OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_default(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        thread: std::thread::current()
+            .name()
+            .unwrap_or("<unnamed>")
+            .to_string(),
+        message,
+        location: panic_info.location().map(ToString::to_string),
+        backtrace: Backtrace::force_capture().to_string(),
+        recent_logs: recent_logs(log_dir),
+        state,
+    }
+}
+
+/// the last lines of the newest log file
+fn recent_logs(log_dir: &Path) -> Vec<String> {
+    let path = match log_files(log_dir)
+        .ok()
+        .and_then(|files| files.into_iter().next())
+    {
+        Some(path) => path,
+        None => return vec![],
+    };
+
+    let mut content = vec![];
+    let result = File::open(path).and_then(|mut file| {
+        let start = file
+            .metadata()?
+            .len()
+            .saturating_sub(CRASH_REPORT_LOG_BYTES);
+        file.seek(SeekFrom::Start(start))?;
+        file.read_to_end(&mut content)?;
+        Ok(start)
+    });
+    let start = match result {
+        Ok(start) => start,
+        Err(_) => return vec![],
+    };
+
+    let content = String::from_utf8_lossy(&content);
+    let lines: Vec<&str> = content
+        .lines()
+        // the first line is cut when reading from the middle of the file
+        .skip(usize::from(start > 0))
+        .collect();
+    lines[lines.len().saturating_sub(CRASH_REPORT_LOG_LINES)..]
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
@@ -0,0 +1,246 @@
+use crate::config::get_config;
+use crate::farmer::farming_started;
+use crate::logs::{is_compressed, log_files, open_log_file};
+use crate::node::{get_runtime_version, node_health, node_traffic};
+use crate::utils::{append_archive_entry, custom_log_dir, get_disk_stats, read_config};
+use crate::volumes::{plot_volume_report, sorted_volumes};
+use crate::watchdog::get_node_incidents;
+use anyhow::Result;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::{json, Map, Value};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use sysinfo::{CpuExt, System, SystemExt};
+use tracing::{error, info};
+
+/// Maximum size of the logs in the bundle, the oldest logs are left out first.
+const MAX_BUNDLE_LOG_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Replaces the values that identify the user or their files.
+const REDACTED: &str = "<redacted>";
+
+/// writes an archive with what is needed to investigate a problem into `destination`: recent
+/// logs and the config without the reward address and paths, node and farmer status, disks and
+/// system details
+#[tauri::command]
+pub(crate) async fn create_diagnostic_bundle(
+    destination: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    // gathered up front, the rest does not need the async runtime
+    let status = json!({
+        "node": node_health().await,
+        "runtimeVersion": get_runtime_version().await,
+        "traffic": node_traffic().await,
+        "incidents": get_node_incidents(),
+        "farmingStarted": farming_started(),
+    });
+
+    let result = tokio::task::spawn_blocking(move || {
+        write_bundle(Path::new(&destination), status, &app_handle)
+    })
+    .await
+    .map_err(|error| format!("diagnostic bundle task failed: {error}"))?;
+
+    match result {
+        Ok(()) => {
+            info!("Created a diagnostic bundle");
+            Ok(())
+        }
+        Err(error) => {
+            error!("Failed to create a diagnostic bundle: {error}");
+            Err(format!(
+                "couldn't create the diagnostic bundle because: {error}"
+            ))
+        }
+    }
+}
+
+fn write_bundle(destination: &Path, status: Value, app_handle: &tauri::AppHandle) -> Result<()> {
+    let mut archive = tar::Builder::new(GzEncoder::new(
+        File::create(destination)?,
+        Compression::default(),
+    ));
+
+    let config = read_config(app_handle.clone())
+        .map_err(anyhow::Error::msg)
+        .and_then(|content| Ok(serde_json::from_str::<Map<String, Value>>(&content)?))
+        .map(|config| Value::Object(redact_config(config)))
+        .unwrap_or_else(|error| json!({ "error": error.to_string() }));
+    append_archive_entry(
+        &mut archive,
+        "config.json",
+        &serde_json::to_vec_pretty(&config)?,
+    )?;
+
+    append_archive_entry(
+        &mut archive,
+        "status.json",
+        &serde_json::to_vec_pretty(&status)?,
+    )?;
+
+    let plot_location = get_config(app_handle.clone())
+        .map(|config| config.plot.location)
+        .unwrap_or_default();
+    let plot_location = plot_location.to_string_lossy().into_owned();
+    let mut disks = if plot_location.is_empty() {
+        json!({ "volumes": sorted_volumes() })
+    } else {
+        json!({
+            "plotDisk": get_disk_stats(plot_location.clone()),
+            "plotDirectory": plot_volume_report(Path::new(&plot_location)),
+            "volumes": sorted_volumes(),
+        })
+    };
+    let redactions = path_redactions(&plot_location);
+    redact_strings(&mut disks, &redactions);
+    append_archive_entry(
+        &mut archive,
+        "disks.json",
+        &serde_json::to_vec_pretty(&disks)?,
+    )?;
+
+    append_archive_entry(
+        &mut archive,
+        "system.json",
+        &serde_json::to_vec_pretty(&system_info())?,
+    )?;
+
+    let log_dir = custom_log_dir(&app_handle.config().tauri.bundle.identifier);
+    append_logs(&mut archive, &log_dir, &redactions)?;
+
+    archive.into_inner()?.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// the reward address and the paths are replaced, the rest is kept to reproduce the setup
+fn redact_config(mut config: Map<String, Value>) -> Map<String, Value> {
+    let redact = |value: Option<&mut Value>| {
+        if let Some(value) = value.filter(|value| !value.is_null()) {
+            *value = REDACTED.into();
+        }
+    };
+
+    redact(config.get_mut("rewardAddress"));
+    redact(config.pointer_mut("/plot/location"));
+    redact(config.pointer_mut("/node/execution/runtimeOverrides"));
+
+    config
+}
+
+/// the plot location, the home directory and the user name in paths, for example in
+/// `/media/<user>/...` mount points, with what replaces them in the bundle
+fn path_redactions(plot_location: &str) -> Vec<(String, String)> {
+    let mut redactions = vec![];
+    if !plot_location.is_empty() {
+        redactions.push((plot_location.to_string(), REDACTED.to_string()));
+    }
+
+    if let Some(home_dir) = dirs::home_dir() {
+        if let Some(user_name) = home_dir.file_name() {
+            let separator = std::path::MAIN_SEPARATOR;
+            redactions.push((
+                home_dir.to_string_lossy().into_owned(),
+                REDACTED.to_string(),
+            ));
+            redactions.push((
+                format!("{separator}{}{separator}", user_name.to_string_lossy()),
+                format!("{separator}{REDACTED}{separator}"),
+            ));
+        }
+    }
+
+    redactions
+}
+
+/// applies the `redactions` to all the strings in `value`, error messages included
+fn redact_strings(value: &mut Value, redactions: &[(String, String)]) {
+    match value {
+        Value::String(text) => {
+            for (from, to) in redactions {
+                *text = text.replace(from, to);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                redact_strings(value, redactions);
+            }
+        }
+        Value::Object(values) => {
+            for value in values.values_mut() {
+                redact_strings(value, redactions);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn system_info() -> Value {
+    let mut system = System::new();
+    system.refresh_memory();
+    system.refresh_cpu();
+
+    json!({
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "osVersion": system.long_os_version(),
+        "kernelVersion": system.kernel_version(),
+        "cpu": system.cpus().first().map(|cpu| cpu.brand().to_string()),
+        "cpuCores": system.cpus().len(),
+        "totalMemoryBytes": system.total_memory(),
+        "availableMemoryBytes": system.available_memory(),
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "substrateVersion": env!("SUBSTRATE_CLI_IMPL_VERSION"),
+    })
+}
+
+/// adds the newest log files to the archive, up to `MAX_BUNDLE_LOG_BYTES`, the file that does not
+/// fit entirely is cut to its end, or left out when compressed
+fn append_logs<W: std::io::Write>(
+    archive: &mut tar::Builder<W>,
+    log_dir: &Path,
+    redactions: &[(String, String)],
+) -> Result<()> {
+    let mut remaining = MAX_BUNDLE_LOG_BYTES;
+    for path in log_files(log_dir).map_err(anyhow::Error::msg)? {
+        if remaining == 0 {
+            break;
+        }
+
+        let mut content = vec![];
+        if is_compressed(&path) {
+            // the end of a compressed file can not be read on its own
+            open_log_file(&path)?.read_to_end(&mut content)?;
+            content.drain(..content.len().saturating_sub(remaining as usize));
+        } else {
+            let mut file = File::open(&path)?;
+            let size = file.metadata()?.len();
+            if size > remaining {
+                file.seek(SeekFrom::Start(size - remaining))?;
+            }
+            file.take(remaining).read_to_end(&mut content)?;
+        }
+
+        let mut content = String::from_utf8_lossy(&content).into_owned();
+        for (from, to) in redactions {
+            content = content.replace(from, to);
+        }
+        remaining = remaining.saturating_sub(content.len() as u64);
+
+        // compressed files are stored decompressed, the paths in them had to be redacted
+        let file_name = if is_compressed(&path) {
+            path.file_stem()
+        } else {
+            path.file_name()
+        };
+        append_archive_entry(
+            archive,
+            Path::new("logs").join(file_name.unwrap_or_default()),
+            content.as_bytes(),
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,8 +1,11 @@
+use crate::config::get_performance_config;
 use anyhow::{anyhow, Error, Result};
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
 use std::future::Future;
+use std::num::NonZeroU16;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use subspace_core_primitives::PublicKey;
 use subspace_farmer::single_disk_farm::{SingleDiskFarm, SingleDiskFarmOptions};
@@ -10,6 +13,7 @@ use subspace_farmer::single_plot_farm::PlotFactoryOptions;
 use subspace_farmer::{NodeRpcClient, Plot, RpcClient};
 use subspace_networking::libp2p::{multiaddr::Protocol, Multiaddr};
 use subspace_networking::{Config, RelayMode};
+use tokio::sync::Notify;
 use tokio::time::{sleep, timeout, Duration};
 use tracing::{debug, error, info, trace, warn};
 
@@ -20,6 +24,15 @@ const GEMINI_2A_GENESIS_HASH: [u8; 32] = [
 // 100GiB
 const GEMINI_2A_MAX_ALLOCATED_SIZE: u64 = 100 * 1024 * 1024 * 1024;
 
+/// File describing the farm, created by the farmer in the plot directory.
+pub(crate) const SINGLE_DISK_FARM_INFO_FILE: &str = "single_disk_farm.json";
+
+/// Notified when the farmer should restart, for example after the node was restarted.
+static FARMER_RESTART: Notify = Notify::const_new();
+
+/// Set once `farming` started the farmer, it keeps restarting it from then on.
+static FARMING_STARTED: AtomicBool = AtomicBool::new(false);
+
 #[derive(Clone)]
 struct FarmingArgs {
     node_rpc_url: String,
@@ -28,6 +41,7 @@ struct FarmingArgs {
     bootstrap_nodes: Vec<Multiaddr>,
     archiving: ArchivingFrom,
     dsn_sync: bool,
+    disk_concurrency: NonZeroU16,
 }
 
 #[derive(Debug, Clone)]
@@ -68,7 +82,10 @@ pub(crate) async fn farming(
     path: String,
     reward_address: String,
     plot_size: u64,
+    app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
+    let performance_settings = get_performance_config(app_handle)?.settings();
+
     if let Ok(address) = parse_reward_address(&reward_address) {
         let farming_args = FarmingArgs {
             node_rpc_url: "ws://127.0.0.1:9947".to_string(),
@@ -79,6 +96,7 @@ pub(crate) async fn farming(
             bootstrap_nodes: vec![],
             archiving: ArchivingFrom::Rpc,
             dsn_sync: false,
+            disk_concurrency: performance_settings.disk_concurrency,
         };
 
         let disk_farms = vec![DiskFarm {
@@ -90,25 +108,17 @@ pub(crate) async fn farming(
         let mut farming_handle = farm(disk_farms.clone(), farming_args.clone())
             .await
             .map_err(|error| format!("farm function failed to start, with error: {error}"))?;
+        FARMING_STARTED.store(true, Ordering::Relaxed);
 
         tokio::spawn(async move {
-            match farming_handle.next().await {
-                Some(Err(error)) => error!("farmer instance crashed with error: {error}"),
-                Some(Ok(_)) => debug!("Node should have been restarted, restarting farmer now"),
-                _ => unreachable!("there should be at least one farming"),
-            }
+            wait_for_farming_exit(&mut farming_handle).await;
+            drop(farming_handle);
             loop {
                 match farm(disk_farms.clone(), farming_args.clone()).await {
                     Err(error) => {
                         error!("farm function failed to start, with error: {error}")
                     }
-                    Ok(mut handle) => match handle.next().await {
-                        Some(Err(error)) => error!("farmer instance crashed with error: {error}"),
-                        Some(Ok(_)) => {
-                            debug!("Node should have been restarted, restarting farmer now")
-                        }
-                        _ => unreachable!("there should be at least one farming"),
-                    },
+                    Ok(mut handle) => wait_for_farming_exit(&mut handle).await,
                 }
             }
         });
@@ -118,6 +128,30 @@ pub(crate) async fn farming(
     Err("could not parse the reward address in the backend".into())
 }
 
+/// whether the farmer was started during this run of the app
+pub(crate) fn farming_started() -> bool {
+    FARMING_STARTED.load(Ordering::Relaxed)
+}
+
+/// asks the running farmer to restart, does nothing if the farmer is not running
+pub(crate) fn request_restart() {
+    FARMER_RESTART.notify_waiters();
+}
+
+/// waits until the farming stops, either by itself or because a restart was requested
+async fn wait_for_farming_exit<F: Future<Output = Result<(), Error>>>(
+    farming_handle: &mut FuturesUnordered<F>,
+) {
+    tokio::select! {
+        result = farming_handle.next() => match result {
+            Some(Err(error)) => error!("farmer instance crashed with error: {error}"),
+            Some(Ok(_)) => debug!("Node should have been restarted, restarting farmer now"),
+            _ => unreachable!("there should be at least one farming"),
+        },
+        _ = FARMER_RESTART.notified() => info!("Restart requested, restarting farmer now"),
+    }
+}
+
 /// Start farming by using plot in specified path and connecting to WebSocket server at specified
 /// address.
 async fn farm(
@@ -144,6 +178,7 @@ async fn farm(
         reward_address,
         dsn_sync,
         archiving,
+        disk_concurrency,
     } = farming_args;
 
     // ping node to discover whether it is listening
@@ -169,6 +204,8 @@ async fn farm(
     let mut recorded_history_segment_size = None;
 
     // Starting the relay server node.
+    // TODO: Apply the same bandwidth limits as the node once `subspace_networking::Config` allows
+    //  customizing its transport, it does not expose throughput counters either.
     let (relay_server_node, mut relay_node_runner) = subspace_networking::create(Config {
         listen_on,
         allow_non_globals_in_dht: true,
@@ -231,7 +268,7 @@ async fn farm(
             metadata_directory: disk_farm.metadata_directory,
             allocated_plotting_space: disk_farm.allocated_plotting_space,
             farmer_protocol_info,
-            disk_concurrency: std::num::NonZeroU16::new(2).expect("hard-coded value is correct"),
+            disk_concurrency,
             archiving_client,
             farming_client,
             reward_address,
@@ -284,7 +321,7 @@ fn raise_fd_limit() {
     }
 }
 
-fn parse_reward_address(s: &str) -> Result<PublicKey, sp_core::crypto::PublicError> {
+pub(crate) fn parse_reward_address(s: &str) -> Result<PublicKey, sp_core::crypto::PublicError> {
     s.parse::<sp_core::sr25519::Public>()
         .map(|key| PublicKey::from(key.0))
 }
@@ -0,0 +1,201 @@
+use crate::config::get_node_config;
+use crate::node::{listen_addresses, lock_stopped_node, network_addresses, node_key_path};
+use sc_network::config::identity::{ed25519, PublicKey};
+use sc_network::config::MultiaddrWithPeerId;
+use sc_network::multiaddr::Protocol;
+use sc_network::Multiaddr;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::iter;
+use std::net::IpAddr;
+#[cfg(not(target_os = "windows"))]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use tracing::info;
+
+/// Identity of the node on the network
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NodeIdentity {
+    peer_id: String,
+    /// addresses other peers can dial, with the peer ID: the ones the running node listens on
+    /// and its external addresses, or the IPs of the network interfaces when it is stopped, and
+    /// the public addresses of the config
+    multiaddrs: Vec<String>,
+}
+
+/// returns the peer ID and the multiaddrs of the node under `path`, `None` if the node was never
+/// started and has no key yet
+#[tauri::command]
+pub(crate) async fn get_node_identity(
+    path: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<NodeIdentity>, String> {
+    let key_path = node_key_path(path.into()).map_err(|error| error.to_string())?;
+    if !key_path.exists() {
+        return Ok(None);
+    }
+
+    let secret = read_node_key(&key_path)?;
+    node_identity(&secret, app_handle).await.map(Some)
+}
+
+/// replaces the node key under `path` with a new one, the node gets a new peer ID
+///
+/// the node must be stopped
+#[tauri::command]
+pub(crate) async fn rotate_node_key(
+    path: String,
+    app_handle: tauri::AppHandle,
+) -> Result<NodeIdentity, String> {
+    let _node_handle_guard = lock_stopped_node().await?;
+
+    let key_path = node_key_path(path.into()).map_err(|error| error.to_string())?;
+    let secret = ed25519::SecretKey::generate();
+    write_node_key(&key_path, &secret)?;
+
+    info!("Rotated the node key");
+    node_identity(&secret, app_handle).await
+}
+
+/// replaces the node key under `path` with the one in the `source` file, either 32 raw bytes or
+/// 64 hex characters like the node accepts
+///
+/// the node must be stopped
+#[tauri::command]
+pub(crate) async fn import_node_key(
+    path: String,
+    source: String,
+    app_handle: tauri::AppHandle,
+) -> Result<NodeIdentity, String> {
+    let _node_handle_guard = lock_stopped_node().await?;
+
+    let secret = read_node_key(Path::new(&source))?;
+    let key_path = node_key_path(path.into()).map_err(|error| error.to_string())?;
+    write_node_key(&key_path, &secret)?;
+
+    info!("Imported the node key from {source}");
+    node_identity(&secret, app_handle).await
+}
+
+/// copies the node key under `path` into the `destination` file
+#[tauri::command]
+pub(crate) fn export_node_key(path: String, destination: String) -> Result<(), String> {
+    let key_path = node_key_path(path.into()).map_err(|error| error.to_string())?;
+    if !key_path.exists() {
+        return Err("there is no node key yet, start the node once to create it".into());
+    }
+
+    let secret = read_node_key(&key_path)?;
+    write_node_key(Path::new(&destination), &secret)?;
+
+    info!("Exported the node key into {destination}");
+    Ok(())
+}
+
+async fn node_identity(
+    secret: &ed25519::SecretKey,
+    app_handle: tauri::AppHandle,
+) -> Result<NodeIdentity, String> {
+    let keypair = ed25519::Keypair::from(secret.clone());
+    let peer_id = PublicKey::Ed25519(keypair.public()).to_peer_id();
+    let public_addresses = get_node_config(app_handle)?.peers.public_addresses()?;
+
+    let addresses = match network_addresses().await {
+        Some(addresses) => addresses,
+        None => interface_addresses()?,
+    };
+
+    let mut multiaddrs = vec![];
+    for multiaddr in addresses
+        .into_iter()
+        .filter(is_dialable)
+        .chain(public_addresses)
+    {
+        let multiaddr = MultiaddrWithPeerId { multiaddr, peer_id }.to_string();
+        if !multiaddrs.contains(&multiaddr) {
+            multiaddrs.push(multiaddr);
+        }
+    }
+
+    Ok(NodeIdentity {
+        peer_id: peer_id.to_string(),
+        multiaddrs,
+    })
+}
+
+/// the listen addresses of the node, with the unspecified IP replaced with each IP of the network
+/// interfaces, like the node reports them once it listens
+fn interface_addresses() -> Result<Vec<Multiaddr>, String> {
+    let interfaces = if_addrs::get_if_addrs()
+        .map_err(|why| format!("couldn't list the network interfaces because: {why}"))?;
+
+    let mut addresses = vec![];
+    for listen_address in listen_addresses() {
+        let mut protocols = listen_address.iter();
+        let ip_protocol = protocols.next();
+        let rest: Multiaddr = protocols.collect();
+
+        for interface in &interfaces {
+            let ip_protocol = match (&ip_protocol, interface.ip()) {
+                (Some(Protocol::Ip4(_)), IpAddr::V4(ip)) => Protocol::Ip4(ip),
+                (Some(Protocol::Ip6(_)), IpAddr::V6(ip)) => Protocol::Ip6(ip),
+                _ => continue,
+            };
+            addresses.push(iter::once(ip_protocol).chain(rest.iter()).collect());
+        }
+    }
+
+    Ok(addresses)
+}
+
+/// whether other machines can dial `multiaddr`, loopback and IPv6 link-local addresses are only
+/// reachable from this machine or need an interface scope
+fn is_dialable(multiaddr: &Multiaddr) -> bool {
+    match multiaddr.iter().next() {
+        Some(Protocol::Ip4(ip)) => !ip.is_loopback() && !ip.is_unspecified(),
+        Some(Protocol::Ip6(ip)) => {
+            !ip.is_loopback() && !ip.is_unspecified() && (ip.segments()[0] & 0xffc0) != 0xfe80
+        }
+        _ => true,
+    }
+}
+
+fn read_node_key(path: &Path) -> Result<ed25519::SecretKey, String> {
+    let bytes =
+        fs::read(path).map_err(|why| format!("couldn't read the node key because: {why}"))?;
+    parse_node_key(bytes)
+}
+
+/// mirrors the parsing of `sc_network::config::Secret::File`
+pub(crate) fn parse_node_key(mut bytes: Vec<u8>) -> Result<ed25519::SecretKey, String> {
+    let hex_bytes = std::str::from_utf8(&bytes)
+        .ok()
+        .filter(|content| content.len() == 64)
+        .and_then(|content| hex::decode(content).ok());
+    let result = match hex_bytes {
+        Some(mut hex_bytes) => ed25519::SecretKey::from_bytes(&mut hex_bytes),
+        None => ed25519::SecretKey::from_bytes(&mut bytes),
+    };
+
+    result.map_err(|why| format!("couldn't parse the node key because: {why}"))
+}
+
+pub(crate) fn write_node_key(path: &Path, secret: &ed25519::SecretKey) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|why| format!("couldn't create the node key directory because: {why}"))?;
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // the key is what identifies the node on the network, other users must not be able to read it
+    #[cfg(not(target_os = "windows"))]
+    options.mode(0o600);
+
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(secret.as_ref()))
+        .map_err(|why| format!("couldn't write the node key because: {why}"))
+}
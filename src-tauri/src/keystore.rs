@@ -0,0 +1,154 @@
+use crate::node::keystore_path;
+use sc_keystore::LocalKeystore;
+use serde::Serialize;
+use sp_core::crypto::{CryptoTypePublicPair, KeyTypeId, SecretString};
+use sp_core::{ecdsa, ed25519, sr25519};
+use sp_keystore::SyncCryptoStore;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// A key stored in the filesystem keystore of the node
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct KeystoreKey {
+    /// 4 characters key type, for example `sub_`
+    key_type: String,
+    /// hex encoded public key, with the `0x` prefix
+    public: String,
+}
+
+/// lists the keys in the filesystem keystore, optionally only the ones of the given key type
+#[tauri::command]
+pub(crate) fn list_keys(
+    path: String,
+    key_type: Option<String>,
+) -> Result<Vec<KeystoreKey>, String> {
+    let keystore_path = keystore_path(path.into()).map_err(|error| error.to_string())?;
+    let mut keys = stored_keys(&keystore_path)?;
+    if let Some(key_type) = key_type {
+        keys.retain(|key| key.key_type == key_type);
+    }
+
+    Ok(keys)
+}
+
+/// inserts a key into the filesystem keystore, same as the `author_insertKey` RPC method
+#[tauri::command]
+pub(crate) fn insert_key(
+    path: String,
+    key_type: String,
+    suri: String,
+    public: String,
+    password: Option<String>,
+) -> Result<(), String> {
+    let keystore_path = keystore_path(path.into()).map_err(|error| error.to_string())?;
+    let key_type = parse_key_type(&key_type)?;
+    let public = parse_public(&public)?;
+
+    let keystore = open_keystore(keystore_path, password)?;
+
+    SyncCryptoStore::insert_unknown(&keystore, key_type, &suri, &public)
+        .map_err(|_| "couldn't insert the key into the keystore".to_string())?;
+
+    info!("Inserted a new key into the keystore");
+    Ok(())
+}
+
+/// removes a key from the filesystem keystore
+#[tauri::command]
+pub(crate) fn remove_key(path: String, key_type: String, public: String) -> Result<(), String> {
+    let keystore_path = keystore_path(path.into()).map_err(|error| error.to_string())?;
+    let key_type = parse_key_type(&key_type)?;
+    let public = parse_public(&public)?;
+
+    let key_file = key_file_path(keystore_path, key_type, &public);
+    if !key_file.exists() {
+        return Err("there is no such key in the keystore".into());
+    }
+
+    match fs::remove_file(key_file) {
+        Ok(_) => {
+            info!("Removed a key from the keystore");
+            Ok(())
+        }
+        Err(why) => Err(format!("couldn't remove key because: {why}")),
+    }
+}
+
+/// opens the filesystem keystore under `keystore_path`, fails if `password` is not the one the
+/// keys already in there were inserted with, the node could not use keys with another password
+pub(crate) fn open_keystore(
+    keystore_path: PathBuf,
+    password: Option<String>,
+) -> Result<LocalKeystore, String> {
+    let stored_keys = stored_keys(&keystore_path)?;
+    let keystore = LocalKeystore::open(keystore_path, password.map(SecretString::new))
+        .map_err(|why| format!("couldn't open the keystore because: {why}"))?;
+
+    // the key file only has the secret phrase, the password is part of the key derivation, so
+    // a key of the keystore can only be used with the password it was inserted with
+    if let Some(key) = stored_keys.first() {
+        let key_type = parse_key_type(&key.key_type)?;
+        let public = parse_public(&key.public)?;
+        let usable = [sr25519::CRYPTO_ID, ed25519::CRYPTO_ID, ecdsa::CRYPTO_ID]
+            .into_iter()
+            .any(|crypto_id| {
+                let public = CryptoTypePublicPair(crypto_id, public.clone());
+                matches!(
+                    SyncCryptoStore::sign_with(&keystore, key_type, &public, b"password check"),
+                    Ok(Some(_))
+                )
+            });
+        if !usable {
+            return Err("the password does not match the one of the keys in the keystore".into());
+        }
+    }
+
+    Ok(keystore)
+}
+
+/// the keys in the filesystem keystore under `keystore_path`
+fn stored_keys(keystore_path: &Path) -> Result<Vec<KeystoreKey>, String> {
+    // keystore is created lazily by the node, there are no keys if it is missing
+    if !keystore_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let entries = fs::read_dir(keystore_path)
+        .map_err(|why| format!("couldn't read the keystore because: {why}"))?;
+
+    // every key is stored in a file named `hex(key_type) ++ hex(public)`
+    Ok(entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str().and_then(parse_key_file_name))
+        .collect())
+}
+
+/// mirrors the file naming of `sc_keystore::LocalKeystore`
+fn key_file_path(keystore_path: PathBuf, key_type: KeyTypeId, public: &[u8]) -> PathBuf {
+    keystore_path.join(hex::encode(key_type.0) + hex::encode(public).as_str())
+}
+
+fn parse_key_file_name(file_name: &str) -> Option<KeystoreKey> {
+    // `get` instead of `split_at`, other files in the directory may have non-ASCII names
+    let key_type = file_name.get(..8)?;
+    let public = file_name.get(8..).filter(|public| !public.is_empty())?;
+    let key_type = hex::decode(key_type).ok()?;
+    hex::decode(public).ok()?;
+
+    Some(KeystoreKey {
+        key_type: String::from_utf8_lossy(&key_type).into_owned(),
+        public: format!("0x{public}"),
+    })
+}
+
+fn parse_key_type(key_type: &str) -> Result<KeyTypeId, String> {
+    KeyTypeId::try_from(key_type)
+        .map_err(|_| format!("key type must be exactly 4 characters, got: {key_type}"))
+}
+
+fn parse_public(public: &str) -> Result<Vec<u8>, String> {
+    hex::decode(public.trim_start_matches("0x"))
+        .map_err(|why| format!("couldn't parse the public key because: {why}"))
+}
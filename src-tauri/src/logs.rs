@@ -0,0 +1,944 @@
+use crate::config::{
+    get_log_retention_config, read_section, write_section, LogRetentionConfig, LOG_FILTER_KEY,
+};
+use crate::utils::custom_log_dir;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Mutex;
+use tauri::Manager;
+use time::format_description::{self, well_known::Rfc3339};
+use time::{Date, OffsetDateTime};
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::time::{sleep, Duration};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::{debug, error, info, trace, warn, Event, Level, Subscriber};
+use tracing_subscriber::filter::Directive;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+/// Prefix of the log files, the hourly rolling appends the date and the hour to it.
+pub(crate) const LOG_FILE_PREFIX: &str = "subspace-desktop.log";
+
+/// Extension added to the compressed log files.
+const COMPRESSED_LOG_EXTENSION: &str = "gz";
+
+/// Interval between two applications of the log retention settings.
+const LOG_RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Maximum number of entries returned by one `query_logs` call.
+const MAX_QUERY_LIMIT: usize = 1000;
+
+/// Directives always applied before the ones of the user.
+const DEFAULT_LOG_DIRECTIVES: &[&str] = &["subspace_farmer=debug"];
+
+/// Handle to replace the filter of all the log layers, set once the logger is initialized.
+static LOG_FILTER_HANDLE: Mutex<Option<reload::Handle<EnvFilter, Registry>>> = Mutex::new(None);
+
+/// Event emitted to the frontend with batches of live log entries.
+const LOG_STREAM_EVENT: &str = "log_entries";
+
+/// Number of live log entries waiting to be sent to the frontend, the next ones are dropped.
+const LOG_STREAM_CAPACITY: usize = 512;
+
+/// Interval between two batches of live log entries.
+const LOG_STREAM_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Minimum level of the entries streamed to the frontend, see `stream_level`.
+static STREAM_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Live log entries dropped since the last batch, because the frontend could not keep up.
+static DROPPED_ENTRIES: AtomicU64 = AtomicU64::new(0);
+
+/// Target of the entries recorded by `frontend_log`.
+const FRONTEND_TARGET: &str = "frontend";
+
+/// Field of the `frontend` entries holding the fields of the frontend as one JSON object.
+const FRONTEND_FIELDS_KEY: &str = "fields";
+
+/// Fields bunyan adds to every record, they are not returned among the event fields.
+const BUNYAN_FIELDS: &[&str] = &[
+    "v", "name", "msg", "level", "hostname", "pid", "time", "target",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::TRACE => Self::Trace,
+            Level::DEBUG => Self::Debug,
+            Level::INFO => Self::Info,
+            Level::WARN => Self::Warn,
+            Level::ERROR => Self::Error,
+        }
+    }
+}
+
+impl LogLevel {
+    /// mirrors the level mapping of `tracing-bunyan-formatter`
+    fn from_bunyan(level: u64) -> Option<Self> {
+        match level {
+            10 => Some(Self::Trace),
+            20 => Some(Self::Debug),
+            30 => Some(Self::Info),
+            40 => Some(Self::Warn),
+            50 => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Filters of `query_logs`, all of them are optional
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct LogQuery {
+    /// minimum level of the entries
+    level: Option<LogLevel>,
+    /// target or module prefix, for example `subspace_farmer`
+    target: Option<String>,
+    /// RFC 3339 timestamps, both inclusive
+    from: Option<String>,
+    to: Option<String>,
+    /// case-insensitive text searched in the message and the field values
+    text: Option<String>,
+    /// values the fields must have, for example `{"component": "dashboard"}`
+    fields: Map<String, Value>,
+    /// number of matching entries to skip, newest first
+    offset: usize,
+    limit: usize,
+}
+
+impl Default for LogQuery {
+    fn default() -> Self {
+        Self {
+            level: None,
+            target: None,
+            from: None,
+            to: None,
+            text: None,
+            fields: Map::new(),
+            offset: 0,
+            limit: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LogEntry {
+    time: String,
+    level: LogLevel,
+    target: String,
+    message: String,
+    fields: Map<String, Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LogPage {
+    /// newest first
+    entries: Vec<LogEntry>,
+    /// whether there are older matching entries after this page
+    has_more: bool,
+}
+
+/// Entry sent by the frontend through `frontend_log`
+#[derive(Debug, Deserialize)]
+pub(crate) struct FrontendLogEntry {
+    level: LogLevel,
+    /// part of the UI the entry comes from, for example `dashboard`
+    component: String,
+    message: String,
+    #[serde(default)]
+    fields: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogBatch {
+    entries: Vec<LogEntry>,
+    /// entries dropped since the previous batch
+    dropped: u64,
+}
+
+/// returns the log filter directives of the user, applied on top of `RUST_LOG` and the defaults
+#[tauri::command]
+pub(crate) fn get_log_filter(app_handle: tauri::AppHandle) -> Result<String, String> {
+    read_section(&app_handle, LOG_FILTER_KEY)
+}
+
+/// replaces the log filter directives of the user, for example `subspace_farmer=trace,info`,
+/// the new filter applies immediately and is kept for the next launches
+#[tauri::command]
+pub(crate) fn set_log_filter(
+    directives: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let filter = log_filter(&directives)?;
+    write_section(&app_handle, LOG_FILTER_KEY, &directives)?;
+    reload_log_filter(filter)?;
+
+    info!("Log filter changed to `{directives}`");
+    Ok(())
+}
+
+/// the filter of all the log layers, with the user `directives` on top of the defaults
+pub(crate) fn log_filter(directives: &str) -> Result<EnvFilter, String> {
+    let mut filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .from_env_lossy();
+    for directive in DEFAULT_LOG_DIRECTIVES {
+        filter = filter.add_directive(directive.parse().expect("hard-coded directive is correct"));
+    }
+    // a directive for the same target replaces the default one
+    for directive in parse_log_directives(directives)? {
+        filter = filter.add_directive(directive);
+    }
+
+    Ok(filter)
+}
+
+pub(crate) fn parse_log_directives(directives: &str) -> Result<Vec<Directive>, String> {
+    directives
+        .split(',')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .map(|directive| {
+            directive
+                .parse()
+                .map_err(|why| format!("invalid log directive `{directive}`: {why}"))
+        })
+        .collect()
+}
+
+/// keeps the handle of the reloadable filter created in `main`
+pub(crate) fn set_log_filter_handle(handle: reload::Handle<EnvFilter, Registry>) {
+    *LOG_FILTER_HANDLE
+        .lock()
+        .expect("log filter lock is never poisoned") = Some(handle);
+}
+
+/// applies the directives stored in the config, the logger starts with the defaults only
+pub(crate) fn apply_stored_log_filter(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let directives: String = read_section(app_handle, LOG_FILTER_KEY)?;
+    if directives.is_empty() {
+        return Ok(());
+    }
+    reload_log_filter(log_filter(&directives)?)
+}
+
+fn reload_log_filter(filter: EnvFilter) -> Result<(), String> {
+    match LOG_FILTER_HANDLE
+        .lock()
+        .expect("log filter lock is never poisoned")
+        .as_ref()
+    {
+        Some(handle) => handle
+            .reload(filter)
+            .map_err(|why| format!("couldn't change the log filter because: {why}")),
+        None => Err("the logger is not initialized".into()),
+    }
+}
+
+/// starts streaming the log entries at or above `level` to the frontend, replaces the level of a
+/// previous subscription
+#[tauri::command]
+pub(crate) fn subscribe_logs(level: LogLevel) {
+    STREAM_LEVEL.store(level as u8 + 1, Ordering::Relaxed);
+}
+
+#[tauri::command]
+pub(crate) fn unsubscribe_logs() {
+    STREAM_LEVEL.store(0, Ordering::Relaxed);
+}
+
+/// records the `entries` buffered by the frontend under the `frontend` target
+///
+/// tracing field names are static, so the fields of an entry are recorded together as one JSON
+/// object in the `fields` field, `expand_frontend_fields` turns them back into fields of the entry
+#[tauri::command]
+pub(crate) fn frontend_log(entries: Vec<FrontendLogEntry>) {
+    for entry in entries {
+        let FrontendLogEntry {
+            level,
+            component,
+            message,
+            fields,
+        } = entry;
+        let fields = Value::Object(fields);
+
+        match level {
+            LogLevel::Trace => trace!(target: FRONTEND_TARGET, %component, %fields, "{message}"),
+            LogLevel::Debug => debug!(target: FRONTEND_TARGET, %component, %fields, "{message}"),
+            LogLevel::Info => info!(target: FRONTEND_TARGET, %component, %fields, "{message}"),
+            LogLevel::Warn => warn!(target: FRONTEND_TARGET, %component, %fields, "{message}"),
+            LogLevel::Error => error!(target: FRONTEND_TARGET, %component, %fields, "{message}"),
+        }
+    }
+}
+
+/// replaces the JSON object recorded by `frontend_log` in the `fields` field of a `frontend` entry
+/// with its keys, the fields recorded by the backend win over the frontend ones of the same name
+fn expand_frontend_fields(target: &str, fields: &mut Map<String, Value>) {
+    if target != FRONTEND_TARGET {
+        return;
+    }
+    let frontend_fields = match fields.get(FRONTEND_FIELDS_KEY) {
+        Some(Value::String(json)) => match serde_json::from_str(json) {
+            Ok(Value::Object(frontend_fields)) => frontend_fields,
+            _ => return,
+        },
+        _ => return,
+    };
+
+    fields.remove(FRONTEND_FIELDS_KEY);
+    for (key, value) in frontend_fields {
+        fields.entry(key).or_insert(value);
+    }
+}
+
+/// `None` while the frontend is not subscribed
+fn stream_level() -> Option<LogLevel> {
+    match STREAM_LEVEL.load(Ordering::Relaxed) {
+        0 => None,
+        1 => Some(LogLevel::Trace),
+        2 => Some(LogLevel::Debug),
+        3 => Some(LogLevel::Info),
+        4 => Some(LogLevel::Warn),
+        _ => Some(LogLevel::Error),
+    }
+}
+
+/// Layer forwarding the log entries to the frontend while it is subscribed
+///
+/// Entries go through a bounded channel and are sent in batches by `spawn_log_stream`, entries
+/// that do not fit in the channel are dropped and counted.
+pub(crate) struct LogStreamLayer {
+    sender: Sender<LogEntry>,
+}
+
+/// creates the layer and the receiving end to pass to `spawn_log_stream`
+pub(crate) fn log_stream() -> (LogStreamLayer, Receiver<LogEntry>) {
+    let (sender, receiver) = mpsc::channel(LOG_STREAM_CAPACITY);
+    (LogStreamLayer { sender }, receiver)
+}
+
+impl<S: Subscriber> Layer<S> for LogStreamLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = LogLevel::from(*metadata.level());
+        if !stream_level().map_or(false, |min_level| level >= min_level) {
+            return;
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        expand_frontend_fields(metadata.target(), &mut visitor.fields);
+        let entry = LogEntry {
+            time: OffsetDateTime::now_utc()
+                .format(&Rfc3339)
+                .unwrap_or_default(),
+            level,
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        };
+
+        if self.sender.try_send(entry).is_err() {
+            DROPPED_ENTRIES.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: Map<String, Value>,
+}
+
+impl FieldVisitor {
+    fn record_value(&mut self, field: &Field, value: Value) {
+        match (field.name(), value) {
+            ("message", Value::String(message)) => self.message = message,
+            (name, value) => {
+                self.fields.insert(name.to_string(), value);
+            }
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record_value(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_value(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record_value(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record_value(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_value(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_value(field, format!("{value:?}").into());
+    }
+}
+
+/// sends the entries of the `log_stream` layer to the frontend, in batches
+pub(crate) fn spawn_log_stream(mut receiver: Receiver<LogEntry>, app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        while let Some(entry) = receiver.recv().await {
+            // let the batch fill up, the channel bounds its size
+            sleep(LOG_STREAM_INTERVAL).await;
+
+            let mut entries = vec![entry];
+            while let Ok(entry) = receiver.try_recv() {
+                entries.push(entry);
+            }
+            let batch = LogBatch {
+                entries,
+                dropped: DROPPED_ENTRIES.swap(0, Ordering::Relaxed),
+            };
+
+            if let Err(error) = app_handle.emit_all(LOG_STREAM_EVENT, batch) {
+                error!("Failed to emit {LOG_STREAM_EVENT}: {error}");
+            }
+        }
+    });
+}
+
+/// searches the log files for entries matching `query`
+#[tauri::command]
+pub(crate) async fn query_logs(
+    query: LogQuery,
+    app_handle: tauri::AppHandle,
+) -> Result<LogPage, String> {
+    let log_dir = custom_log_dir(&app_handle.config().tauri.bundle.identifier);
+
+    tokio::task::spawn_blocking(move || search_logs(&log_dir, query))
+        .await
+        .map_err(|error| format!("log query task failed: {error}"))?
+}
+
+struct LogFilter {
+    level: Option<LogLevel>,
+    target: Option<String>,
+    from: Option<OffsetDateTime>,
+    to: Option<OffsetDateTime>,
+    text: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl LogFilter {
+    fn new(query: &LogQuery) -> Result<Self, String> {
+        let parse_time = |time: &Option<String>| {
+            time.as_deref()
+                .map(|time| OffsetDateTime::parse(time, &Rfc3339))
+                .transpose()
+                .map_err(|why| format!("couldn't parse the time range because: {why}"))
+        };
+
+        Ok(Self {
+            level: query.level,
+            target: query.target.clone().filter(|target| !target.is_empty()),
+            from: parse_time(&query.from)?,
+            to: parse_time(&query.to)?,
+            text: query
+                .text
+                .as_ref()
+                .filter(|text| !text.is_empty())
+                .map(|text| text.to_lowercase()),
+            fields: query.fields.clone(),
+        })
+    }
+
+    /// parses a line of a log file, `None` if it is not valid or does not match
+    fn apply(&self, line: &str) -> Option<LogEntry> {
+        // cheap rejection before parsing, the text may be escaped in the JSON though
+        if let Some(text) = &self.text {
+            if !line.to_lowercase().contains(text.as_str()) && !line.contains('\\') {
+                return None;
+            }
+        }
+
+        let mut record: Map<String, Value> = serde_json::from_str(line).ok()?;
+
+        let level = record
+            .get("level")
+            .and_then(Value::as_u64)
+            .and_then(LogLevel::from_bunyan)?;
+        if self.level.map_or(false, |min_level| level < min_level) {
+            return None;
+        }
+
+        let target = record.get("target").and_then(Value::as_str)?.to_string();
+        if let Some(prefix) = &self.target {
+            if !target.starts_with(prefix.as_str()) {
+                return None;
+            }
+        }
+
+        let time = record.get("time").and_then(Value::as_str)?.to_string();
+        if self.from.is_some() || self.to.is_some() {
+            let timestamp = OffsetDateTime::parse(&time, &Rfc3339).ok()?;
+            if self.from.map_or(false, |from| timestamp < from)
+                || self.to.map_or(false, |to| timestamp > to)
+            {
+                return None;
+            }
+        }
+
+        let message = record
+            .get("msg")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        record.retain(|key, _| !BUNYAN_FIELDS.contains(&key.as_str()));
+        expand_frontend_fields(&target, &mut record);
+
+        if self
+            .fields
+            .iter()
+            .any(|(key, value)| record.get(key) != Some(value))
+        {
+            return None;
+        }
+
+        if let Some(text) = &self.text {
+            let matches = message.to_lowercase().contains(text.as_str())
+                || record.values().any(|value| match value {
+                    Value::String(value) => value.to_lowercase().contains(text.as_str()),
+                    value => value.to_string().to_lowercase().contains(text.as_str()),
+                });
+            if !matches {
+                return None;
+            }
+        }
+
+        Some(LogEntry {
+            time,
+            level,
+            target,
+            message,
+            fields: record,
+        })
+    }
+}
+
+fn search_logs(log_dir: &Path, query: LogQuery) -> Result<LogPage, String> {
+    let filter = LogFilter::new(&query)?;
+    let limit = query.limit.min(MAX_QUERY_LIMIT);
+    // matching entries to find, newest first, before the page is complete
+    let needed = query.offset + limit;
+
+    let mut entries = vec![];
+    // matching entries in the files already searched
+    let mut matched = 0;
+    for path in log_files(log_dir)? {
+        if matched > needed {
+            break;
+        }
+
+        let compressed = is_compressed(&path);
+        let file = match open_log_file(&path) {
+            Ok(file) => file,
+            Err(error) => {
+                warn!("Failed to open the log file {}: {error}", path.display());
+                continue;
+            }
+        };
+
+        // files are read oldest entry first, only the newest matches of the file can be needed
+        let remaining = needed - matched;
+        let mut newest_matches = VecDeque::with_capacity(remaining.min(MAX_QUERY_LIMIT));
+        let mut file_matched = 0;
+        for line in BufReader::new(file).lines() {
+            let line = match line {
+                Ok(line) => line,
+                // a line cut by a crash, or a file being written to
+                Err(error) if error.kind() == io::ErrorKind::InvalidData && !compressed => continue,
+                // the rest of the file can not be read, most likely a truncated archive
+                Err(error) => {
+                    warn!("Failed to read the log file {}: {error}", path.display());
+                    break;
+                }
+            };
+            if let Some(entry) = filter.apply(&line) {
+                file_matched += 1;
+                if remaining == 0 {
+                    continue;
+                }
+                if newest_matches.len() == remaining {
+                    newest_matches.pop_front();
+                }
+                newest_matches.push_back(entry);
+            }
+        }
+
+        for (index, entry) in newest_matches.into_iter().rev().enumerate() {
+            if matched + index >= query.offset {
+                entries.push(entry);
+            }
+        }
+        matched += file_matched;
+    }
+
+    Ok(LogPage {
+        entries,
+        has_more: matched > needed,
+    })
+}
+
+/// opens a log file for reading, compressed or not
+pub(crate) fn open_log_file(path: &Path) -> io::Result<Box<dyn Read>> {
+    let file = File::open(path)?;
+    if is_compressed(path) {
+        Ok(Box::new(GzDecoder::new(file)))
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
+pub(crate) fn is_compressed(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |extension| extension == COMPRESSED_LOG_EXTENSION)
+}
+
+/// applies the retention settings of the config every hour: compresses the files of the
+/// previous hours, then removes the ones that are too old or do not fit in the size limit
+pub(crate) fn spawn_log_retention(log_dir: PathBuf, app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        loop {
+            let config = get_log_retention_config(app_handle.clone()).unwrap_or_else(|error| {
+                warn!("Log retention could not read the config: {error}");
+                LogRetentionConfig::default()
+            });
+
+            let log_dir = log_dir.clone();
+            let result =
+                tokio::task::spawn_blocking(move || apply_log_retention(&log_dir, &config)).await;
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => error!("Failed to apply the log retention: {error}"),
+                Err(error) => error!("Log retention task failed: {error}"),
+            }
+
+            sleep(LOG_RETENTION_INTERVAL).await;
+        }
+    });
+}
+
+fn apply_log_retention(log_dir: &Path, config: &LogRetentionConfig) -> Result<(), String> {
+    let mut files = log_files(log_dir)?;
+    // the newest file is the one being written to, it is never touched
+    if files.is_empty() {
+        return Ok(());
+    }
+    let current_file = files.remove(0);
+
+    if config.compress {
+        for path in files.iter_mut().filter(|path| !is_compressed(path)) {
+            match compress_log_file(path) {
+                Ok(compressed_path) => *path = compressed_path,
+                Err(error) => warn!("Failed to compress {}: {error}", path.display()),
+            }
+        }
+    }
+
+    let max_age = Duration::from_secs(config.max_age_days * 24 * 60 * 60);
+    let mut total_size = fs::metadata(&current_file).map_or(0, |metadata| metadata.len());
+    for path in files {
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+        total_size += metadata.len();
+
+        // compressing a file updates its modification time, the date in its name is kept
+        let age = match log_file_date(&path) {
+            Some(date) => (OffsetDateTime::now_utc().date() - date).try_into().ok(),
+            None => metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.elapsed().ok()),
+        };
+        let too_old = age.map_or(false, |age| age > max_age);
+        let too_large = total_size > config.max_total_size_mib * 1024 * 1024;
+        if too_old || too_large {
+            info!("Removing the old log file {}", path.display());
+            if let Err(error) = fs::remove_file(&path) {
+                warn!("Failed to remove {}: {error}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// returns the day of the entries of a log file, from its name: `<prefix>.2022-10-19-13[.gz]`,
+/// or `<prefix>.2022-10-19[.gz]` for the files of the daily rolling used before
+fn log_file_date(path: &Path) -> Option<Date> {
+    let date = path
+        .file_name()?
+        .to_str()?
+        .strip_prefix(LOG_FILE_PREFIX)?
+        .strip_prefix('.')?
+        .get(..10)?;
+    let format = format_description::parse("[year]-[month]-[day]").ok()?;
+    Date::parse(date, &format).ok()
+}
+
+/// replaces a log file with its gzip-compressed version, returns the path of the new file
+fn compress_log_file(path: &Path) -> io::Result<PathBuf> {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(COMPRESSED_LOG_EXTENSION);
+    let compressed_path = path.with_file_name(&file_name);
+    // not picked up as a log file until it is complete
+    file_name.push(".tmp");
+    let temp_path = path.with_file_name(file_name);
+
+    let result = (|| {
+        let mut encoder = GzEncoder::new(File::create(&temp_path)?, Compression::default());
+        io::copy(&mut File::open(path)?, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+        fs::rename(&temp_path, &compressed_path)
+    })();
+    if let Err(error) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error);
+    }
+
+    fs::remove_file(path)?;
+    Ok(compressed_path)
+}
+
+/// returns the log files, newest first
+pub(crate) fn log_files(log_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = match fs::read_dir(log_dir) {
+        Ok(entries) => entries,
+        Err(why) => return Err(format!("couldn't read the log directory because: {why}")),
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|file_name| file_name.to_str())
+                .map_or(false, |file_name| {
+                    file_name.starts_with(LOG_FILE_PREFIX) && !file_name.ends_with(".tmp")
+                })
+        })
+        .collect();
+    // the date suffix sorts chronologically once the compression extension is left out, a
+    // `.2022-10-19.gz` file of the daily rolling used before is older than `.2022-10-19-13`
+    files.sort_unstable_by_key(|path| {
+        let mut name = path.clone();
+        if is_compressed(&name) {
+            name.set_extension("");
+        }
+        std::cmp::Reverse(name)
+    });
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// an empty directory of its own for each test, tests run in parallel
+    fn test_log_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "subspace-desktop-logs-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// bunyan lines with the messages `entry <index>`, oldest first
+    fn log_lines(indexes: std::ops::Range<usize>) -> String {
+        indexes
+            .map(|index| {
+                format!(
+                    "{{\"v\":0,\"name\":\"subspace-desktop\",\"msg\":\"entry {index}\",\
+                    \"level\":30,\"hostname\":\"test\",\"pid\":1,\
+                    \"time\":\"2022-10-19T13:00:{:02}Z\",\"target\":\"subspace_desktop\"}}\n",
+                    index % 60
+                )
+            })
+            .collect()
+    }
+
+    fn page_messages(log_dir: &Path, offset: usize, limit: usize) -> (Vec<String>, bool) {
+        let page = search_logs(
+            log_dir,
+            LogQuery {
+                offset,
+                limit,
+                ..LogQuery::default()
+            },
+        )
+        .unwrap();
+        let messages = page
+            .entries
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect();
+        (messages, page.has_more)
+    }
+
+    fn messages(indexes: &[usize]) -> Vec<String> {
+        indexes
+            .iter()
+            .map(|index| format!("entry {index}"))
+            .collect()
+    }
+
+    #[test]
+    fn search_logs_pages_across_files_newest_first() {
+        let log_dir = test_log_dir("paging");
+        // entries 0 to 4 in a compressed older file, 5 to 9 in the current one
+        let mut encoder = GzEncoder::new(
+            File::create(log_dir.join(format!("{LOG_FILE_PREFIX}.2022-10-19-12.gz"))).unwrap(),
+            Compression::default(),
+        );
+        encoder.write_all(log_lines(0..5).as_bytes()).unwrap();
+        encoder.finish().unwrap();
+        fs::write(
+            log_dir.join(format!("{LOG_FILE_PREFIX}.2022-10-19-13")),
+            log_lines(5..10),
+        )
+        .unwrap();
+
+        assert_eq!(page_messages(&log_dir, 0, 3), (messages(&[9, 8, 7]), true));
+        // the page starts in the newer file and ends in the older one
+        assert_eq!(
+            page_messages(&log_dir, 3, 4),
+            (messages(&[6, 5, 4, 3]), true)
+        );
+        // the last page is complete, there is nothing after it
+        assert_eq!(
+            page_messages(&log_dir, 6, 4),
+            (messages(&[3, 2, 1, 0]), false)
+        );
+        assert_eq!(page_messages(&log_dir, 8, 4), (messages(&[1, 0]), false));
+        assert_eq!(page_messages(&log_dir, 10, 4), (vec![], false));
+
+        fs::remove_dir_all(log_dir).unwrap();
+    }
+
+    #[test]
+    fn search_logs_has_more_counts_only_matches() {
+        let log_dir = test_log_dir("matches");
+        let mut lines = log_lines(0..4);
+        // neither a bunyan entry nor a match, must not make the page look incomplete
+        lines.push_str("not a log entry\n");
+        fs::write(
+            log_dir.join(format!("{LOG_FILE_PREFIX}.2022-10-19-13")),
+            lines,
+        )
+        .unwrap();
+
+        assert_eq!(
+            page_messages(&log_dir, 0, 4),
+            (messages(&[3, 2, 1, 0]), false)
+        );
+        assert_eq!(page_messages(&log_dir, 0, 3), (messages(&[3, 2, 1]), true));
+        // no entries are returned, but the caller can still tell there are more
+        assert_eq!(page_messages(&log_dir, 0, 0), (vec![], true));
+
+        fs::remove_dir_all(log_dir).unwrap();
+    }
+
+    #[test]
+    fn log_files_sorts_hourly_daily_and_compressed_files_newest_first() {
+        let log_dir = test_log_dir("ordering");
+        for suffix in [
+            "2022-10-18.gz",
+            "2022-10-19-02.gz",
+            "2022-10-19.gz",
+            "2022-10-19-13",
+            "2022-10-19-00.gz",
+            // being compressed, not a log file yet
+            "2022-10-19-12.gz.tmp",
+        ] {
+            fs::write(log_dir.join(format!("{LOG_FILE_PREFIX}.{suffix}")), "").unwrap();
+        }
+        fs::write(log_dir.join("crash-report.json"), "").unwrap();
+
+        let file_names: Vec<String> = log_files(&log_dir)
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        let expected: Vec<String> = [
+            "2022-10-19-13",
+            "2022-10-19-02.gz",
+            "2022-10-19-00.gz",
+            // the daily file was written before the hourly rolling of the same day
+            "2022-10-19.gz",
+            "2022-10-18.gz",
+        ]
+        .iter()
+        .map(|suffix| format!("{LOG_FILE_PREFIX}.{suffix}"))
+        .collect();
+        assert_eq!(file_names, expected);
+
+        fs::remove_dir_all(log_dir).unwrap();
+    }
+
+    #[test]
+    fn log_file_date_reads_hourly_and_daily_names() {
+        let date = |file_name: String| log_file_date(Path::new(&file_name));
+        let october = |day| Date::from_calendar_date(2022, time::Month::October, day).ok();
+
+        assert_eq!(
+            date(format!("{LOG_FILE_PREFIX}.2022-10-19-13")),
+            october(19)
+        );
+        assert_eq!(
+            date(format!("{LOG_FILE_PREFIX}.2022-10-19-13.gz")),
+            october(19)
+        );
+        assert_eq!(date(format!("{LOG_FILE_PREFIX}.2022-10-18")), october(18));
+        assert_eq!(
+            date(format!("{LOG_FILE_PREFIX}.2022-10-18.gz")),
+            october(18)
+        );
+
+        assert_eq!(date(LOG_FILE_PREFIX.to_string()), None);
+        assert_eq!(date(format!("{LOG_FILE_PREFIX}.2022-13-01")), None);
+        assert_eq!(date("crash-report.json".to_string()), None);
+    }
+}
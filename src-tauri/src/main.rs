@@ -8,22 +8,31 @@ mod linux;
 #[cfg(target_os = "windows")]
 mod windows;
 
+mod config;
+mod crash;
+mod diagnostics;
 mod farmer;
+mod identity;
+mod keystore;
+mod logs;
 mod menu;
+mod migrations;
 mod node;
+mod node_db;
+mod profile;
 mod utils;
+mod volumes;
+mod watchdog;
 
 use anyhow::Result;
 use std::fs::create_dir_all;
 use tauri::SystemTrayEvent;
 use tauri::{Manager, RunEvent, WindowEvent};
-use tracing::level_filters::LevelFilter;
+use tracing::error;
 use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::Layer;
-use tracing_subscriber::{fmt, fmt::format::FmtSpan, EnvFilter};
-
-const KEEP_LAST_N_DAYS: usize = 7;
+use tracing_subscriber::{fmt, fmt::format::FmtSpan, reload};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -31,39 +40,50 @@ async fn main() -> Result<()> {
     let log_dir = utils::custom_log_dir(&ctx.config().tauri.bundle.identifier);
     create_dir_all(log_dir.clone()).expect("path creation should always succeed");
 
-    let mut file_appender = tracing_appender::rolling::daily(log_dir, "subspace-desktop.log");
-    file_appender.keep_last_n_logs(KEEP_LAST_N_DAYS); // keep the logs of last 7 days only
+    // hourly, so that the size limit of `logs::spawn_log_retention` also bounds a verbose day
+    let file_appender = tracing_appender::rolling::hourly(log_dir.clone(), logs::LOG_FILE_PREFIX);
+
+    // filter for all the logging layers, the directives of the user are applied in `setup`
+    let (filter, filter_handle) =
+        reload::Layer::new(logs::log_filter("").expect("default log filter is correct"));
+    logs::set_log_filter_handle(filter_handle);
 
-    // filter for logging
-    let filter = || {
-        EnvFilter::builder()
-            .with_default_directive(LevelFilter::INFO.into())
-            .from_env_lossy()
-            .add_directive("subspace_farmer=debug".parse().unwrap())
-    };
+    let (log_stream_layer, log_stream) = logs::log_stream();
 
     // start logger, after we acquire the bundle identifier
     tracing_subscriber::registry()
+        .with(filter)
         .with(
             fmt::layer()
                 .with_ansi(!cfg!(windows))
-                .with_span_events(FmtSpan::CLOSE)
-                .with_filter(filter()),
+                .with_span_events(FmtSpan::CLOSE),
         )
         .with(
             BunyanFormattingLayer::new("subspace-desktop".to_owned(), file_appender)
-                .and_then(JsonStorageLayer)
-                .with_filter(filter()),
+                .and_then(JsonStorageLayer),
         )
+        .with(log_stream_layer)
         .init();
 
+    // panics are written to a crash report, offered to the user on the next launch
+    crash::init(log_dir.clone());
+
     // building the application
     let app = tauri::Builder::default()
-        .setup(|app| {
+        .setup(move |app| {
             #[cfg(debug_assertions)] // only include this code on debug builds
             {
                 app.get_window("main").unwrap().open_devtools();
             }
+            if let Err(error) = config::upgrade_config(&app.handle()) {
+                error!("Failed to upgrade the config: {error}");
+            }
+            if let Err(error) = logs::apply_stored_log_filter(&app.handle()) {
+                error!("Failed to apply the stored log filter: {error}");
+            }
+            logs::spawn_log_stream(log_stream, app.handle());
+            logs::spawn_log_retention(log_dir, app.handle());
+            watchdog::spawn(app.handle());
             Ok(())
         })
         .menu(menu::get_menu())
@@ -100,10 +120,45 @@ async fn main() -> Result<()> {
                 farmer::farming,
                 farmer::validate_reward_address,
                 node::start_node,
-                utils::frontend_error_logger,
-                utils::frontend_info_logger,
+                node::stop_node,
+                node::get_runtime_version,
+                node::get_node_throughput,
+                watchdog::get_node_incidents,
+                node_db::export_blocks,
+                node_db::import_blocks,
+                node_db::check_node_db,
+                node_db::purge_chain,
+                config::get_config,
+                config::update_config,
+                config::get_node_config,
+                config::update_node_config,
+                config::get_performance_config,
+                config::update_performance_config,
+                config::get_log_retention_config,
+                config::update_log_retention_config,
+                keystore::list_keys,
+                keystore::insert_key,
+                keystore::remove_key,
+                identity::get_node_identity,
+                identity::rotate_node_key,
+                identity::import_node_key,
+                identity::export_node_key,
+                profile::export_profile,
+                profile::import_profile,
+                logs::frontend_log,
                 utils::open_log_dir,
+                logs::query_logs,
+                logs::subscribe_logs,
+                logs::unsubscribe_logs,
+                logs::get_log_filter,
+                logs::set_log_filter,
+                diagnostics::create_diagnostic_bundle,
+                crash::get_pending_crash_report,
+                crash::save_crash_report,
+                crash::dismiss_crash_report,
                 utils::get_disk_stats,
+                volumes::list_volumes,
+                volumes::check_plot_directory,
                 utils::get_this_binary,
                 utils::write_config,
                 utils::read_config,
@@ -117,10 +172,45 @@ async fn main() -> Result<()> {
                 farmer::farming,
                 farmer::validate_reward_address,
                 node::start_node,
-                utils::frontend_error_logger,
-                utils::frontend_info_logger,
+                node::stop_node,
+                node::get_runtime_version,
+                node::get_node_throughput,
+                watchdog::get_node_incidents,
+                node_db::export_blocks,
+                node_db::import_blocks,
+                node_db::check_node_db,
+                node_db::purge_chain,
+                config::get_config,
+                config::update_config,
+                config::get_node_config,
+                config::update_node_config,
+                config::get_performance_config,
+                config::update_performance_config,
+                config::get_log_retention_config,
+                config::update_log_retention_config,
+                keystore::list_keys,
+                keystore::insert_key,
+                keystore::remove_key,
+                identity::get_node_identity,
+                identity::rotate_node_key,
+                identity::import_node_key,
+                identity::export_node_key,
+                profile::export_profile,
+                profile::import_profile,
+                logs::frontend_log,
                 utils::open_log_dir,
+                logs::query_logs,
+                logs::subscribe_logs,
+                logs::unsubscribe_logs,
+                logs::get_log_filter,
+                logs::set_log_filter,
+                diagnostics::create_diagnostic_bundle,
+                crash::get_pending_crash_report,
+                crash::save_crash_report,
+                crash::dismiss_crash_report,
                 utils::get_disk_stats,
+                volumes::list_volumes,
+                volumes::check_plot_directory,
                 utils::get_this_binary,
                 utils::write_config,
                 utils::read_config,
@@ -137,10 +227,45 @@ async fn main() -> Result<()> {
                 farmer::farming,
                 farmer::validate_reward_address,
                 node::start_node,
-                utils::frontend_error_logger,
-                utils::frontend_info_logger,
+                node::stop_node,
+                node::get_runtime_version,
+                node::get_node_throughput,
+                watchdog::get_node_incidents,
+                node_db::export_blocks,
+                node_db::import_blocks,
+                node_db::check_node_db,
+                node_db::purge_chain,
+                config::get_config,
+                config::update_config,
+                config::get_node_config,
+                config::update_node_config,
+                config::get_performance_config,
+                config::update_performance_config,
+                config::get_log_retention_config,
+                config::update_log_retention_config,
+                keystore::list_keys,
+                keystore::insert_key,
+                keystore::remove_key,
+                identity::get_node_identity,
+                identity::rotate_node_key,
+                identity::import_node_key,
+                identity::export_node_key,
+                profile::export_profile,
+                profile::import_profile,
+                logs::frontend_log,
                 utils::open_log_dir,
+                logs::query_logs,
+                logs::subscribe_logs,
+                logs::unsubscribe_logs,
+                logs::get_log_filter,
+                logs::set_log_filter,
+                diagnostics::create_diagnostic_bundle,
+                crash::get_pending_crash_report,
+                crash::save_crash_report,
+                crash::dismiss_crash_report,
                 utils::get_disk_stats,
+                volumes::list_volumes,
+                volumes::check_plot_directory,
                 utils::get_this_binary,
                 utils::write_config,
                 utils::read_config,
@@ -0,0 +1,135 @@
+use crate::node::{DEFAULT_NETWORK_CONFIG_PATH, NODE_KEY_ED25519_FILE};
+use crate::utils::write_atomically;
+use anyhow::{Context, Result};
+use sc_service::BasePath;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+/// File in the base directory recording the migrations applied to it.
+const MIGRATIONS_FILE: &str = "subspace-desktop-migrations.json";
+
+/// A one-off change to the data under the base directory (node database, network key, plots)
+struct Migration {
+    /// migrations are applied in increasing order of their versions, never reuse a version
+    version: u32,
+    name: &'static str,
+    run: fn(&Path) -> Result<()>,
+}
+
+/// All migrations, ordered by version. Append new migrations at the end.
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "reset gemini-1b node key",
+    run: reset_gemini_1b_node_key,
+}];
+
+#[derive(Default, Serialize, Deserialize)]
+struct MigrationsRecord {
+    applied: Vec<AppliedMigration>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AppliedMigration {
+    version: u32,
+    name: String,
+    /// seconds since the unix epoch
+    applied_at: u64,
+}
+
+impl MigrationsRecord {
+    fn load(base_directory: &Path) -> Result<Self> {
+        let path = base_directory.join(MIGRATIONS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("couldn't parse {}", path.display())),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error).with_context(|| format!("couldn't read {}", path.display())),
+        }
+    }
+
+    fn save(&self, base_directory: &Path) -> Result<()> {
+        let path = base_directory.join(MIGRATIONS_FILE);
+        // a torn record would fail to parse and stop the app from starting
+        write_atomically(&path, serde_json::to_string_pretty(self)?.as_bytes())
+            .with_context(|| format!("couldn't write {}", path.display()))
+    }
+
+    fn last_version(&self) -> u32 {
+        self.applied
+            .iter()
+            .map(|migration| migration.version)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// applies the migrations that were not applied to the base directory yet, in order
+///
+/// stops at the first failing migration, the ones before it stay recorded as applied
+pub(crate) fn run_pending_migrations(base_directory: &Path) -> Result<()> {
+    // nothing to migrate on a fresh install
+    if !base_directory.exists() {
+        return Ok(());
+    }
+
+    let mut record = MigrationsRecord::load(base_directory)?;
+    let last_version = record.last_version();
+
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > last_version)
+    {
+        info!(
+            "Applying data migration #{} ({})",
+            migration.version, migration.name
+        );
+        (migration.run)(base_directory).with_context(|| {
+            format!(
+                "migration #{} ({}) failed",
+                migration.version, migration.name
+            )
+        })?;
+
+        record.applied.push(AppliedMigration {
+            version: migration.version,
+            name: migration.name.to_string(),
+            applied_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+        });
+        record.save(base_directory)?;
+        info!("Applied data migration #{}", migration.version);
+    }
+
+    Ok(())
+}
+
+/// nodes upgraded from gemini-1b-2022-jun-08 had to get a new node key
+///
+/// replaces the workaround in `create_full_client`, which checked for the node being stuck at
+/// block #33670; installs that already went through it have the `gemini_1b_workaround` marker
+///
+/// unlike the workaround, the key is removed whatever the best block is: migrations run before
+/// the client opens the database, so the best block is not known yet, and a lost key only gives
+/// the node a new peer id, a new key is generated on the next start. Keys created after the
+/// migration are kept, it is recorded as applied and never runs again
+fn reset_gemini_1b_node_key(base_directory: &Path) -> Result<()> {
+    let network_dir = BasePath::Permanenent(base_directory.to_path_buf())
+        .config_dir("subspace_gemini_1b")
+        .join(DEFAULT_NETWORK_CONFIG_PATH);
+
+    if !network_dir.exists() || network_dir.join("gemini_1b_workaround").exists() {
+        return Ok(());
+    }
+
+    match fs::remove_file(network_dir.join(NODE_KEY_ED25519_FILE)) {
+        Ok(_) => Ok(()),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error.into()),
+    }
+}
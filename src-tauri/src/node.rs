@@ -1,22 +1,33 @@
-use anyhow::{anyhow, Result};
+use crate::config::{
+    get_node_config, get_performance_config, KeystoreKind, NodeConfig, PerformanceConfig,
+    PerformanceSettings,
+};
+use crate::crash;
+use crate::keystore::open_keystore;
+use crate::migrations::run_pending_migrations;
+use anyhow::Result;
 use cirrus_runtime::GenesisConfig as ExecutionGenesisConfig;
-use sc_chain_spec::ChainSpec;
+use futures::StreamExt;
+use sc_chain_spec::{ChainSpec, GetExtension};
 use sc_client_api::HeaderBackend;
 use sc_executor::{
     NativeElseWasmExecutor, NativeExecutionDispatch, WasmExecutionMethod,
     WasmtimeInstantiationStrategy,
 };
-use sc_network::config::{NodeKeyConfig, Secret};
+use sc_network::config::{NodeKeyConfig, NonReservedPeerMode, Secret};
+use sc_network::{Multiaddr, NetworkService};
 use sc_service::config::{
-    ExecutionStrategies, ExecutionStrategy, KeystoreConfig, NetworkConfiguration,
-    OffchainWorkerConfig,
+    ExecutionStrategies, KeystoreConfig, NetworkConfiguration, OffchainWorkerConfig,
 };
 use sc_service::{
-    BasePath, BlocksPruning, Configuration, DatabaseSource, PruningMode, Role, RpcMethods,
-    TracingReceiver,
+    BasePath, BlocksPruning, Configuration, DatabaseSource, PruningMode, Role, TracingReceiver,
 };
-use sc_subspace_chain_specs::ConsensusChainSpec;
-use sp_core::crypto::Ss58AddressFormat;
+use sc_subspace_chain_specs::{ConsensusChainSpec, ExecutionChainSpec};
+use serde::Serialize;
+use sp_core::crypto::{SecretString, Ss58AddressFormat};
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::Block as BlockT;
+use std::any::TypeId;
 use std::env;
 use std::path::PathBuf;
 use std::sync::{Arc, Once, Weak};
@@ -24,26 +35,130 @@ use subspace_fraud_proof::VerifyFraudProof;
 use subspace_runtime::{GenesisConfig as ConsensusGenesisConfig, RuntimeApi};
 use subspace_runtime_primitives::opaque::Block;
 use subspace_service::{FullClient, NewFull, SubspaceConfiguration};
-use tokio::time::{sleep, timeout, Duration};
-use tokio::{runtime::Handle, sync::Mutex, task::JoinHandle};
+use tokio::sync::{Mutex, MutexGuard};
+use tokio::time::{sleep, timeout, Duration, Instant};
+use tokio::{runtime::Handle, task::JoinHandle};
 use tracing::{error, warn};
 
 static INITIALIZE_SUBSTRATE: Once = Once::new();
 
+/// Handle of the running node instance.
+static NODE_HANDLE: Mutex<Option<JoinHandle<()>>> = Mutex::const_new(None);
+
+/// Client of the running node instance, used to know when the node database is released.
+static WEAK_CLIENT: Mutex<Option<Weak<FullClient<RuntimeApi, ExecutorDispatch>>>> =
+    Mutex::const_new(None);
+
+/// Network of the running node instance, used to monitor the peers.
+static WEAK_NETWORK: Mutex<Option<Weak<NodeNetwork>>> = Mutex::const_new(None);
+
+/// Client and network of the running executor node, used to report its status.
+static WEAK_EXECUTOR: Mutex<Option<WeakExecutor>> = Mutex::const_new(None);
+
+/// Time and traffic of the node at the previous `get_node_throughput`, the rates are averaged
+/// since then.
+static LAST_THROUGHPUT_SAMPLE: Mutex<Option<(Instant, NodeTraffic)>> = Mutex::const_new(None);
+
+/// Parameters of the last `start_node`, used to restart the node.
+static START_PARAMS: Mutex<Option<StartParams>> = Mutex::const_new(None);
+
+type NodeNetwork = NetworkService<Block, <Block as BlockT>::Hash>;
+
+type ExecutionBlock = cirrus_runtime::opaque::Block;
+
+type ExecutorClient = sc_service::TFullClient<
+    ExecutionBlock,
+    cirrus_runtime::RuntimeApi,
+    NativeElseWasmExecutor<ExecutionDispatch>,
+>;
+
+type ExecutorNetwork = NetworkService<ExecutionBlock, <ExecutionBlock as BlockT>::Hash>;
+
+type WeakExecutor = (Weak<ExecutorClient>, Weak<ExecutorNetwork>);
+
 /// The maximum number of characters for a node name.
+pub(crate) const MAX_NODE_NAME_LENGTH: usize = 64;
 
 /// Default sub directory to store network config.
-const DEFAULT_NETWORK_CONFIG_PATH: &str = "network";
+pub(crate) const DEFAULT_NETWORK_CONFIG_PATH: &str = "network";
+
+/// Default sub directory of the network's config dir to store the chain database.
+const DEFAULT_DATABASE_PATH: &str = "paritydb";
+
+/// Sub directory of the base directory to store the executor node data.
+const EXECUTOR_BASE_PATH: &str = "executor";
+
+/// Default sub directory of the network's config dir to store the keystore.
+const DEFAULT_KEYSTORE_PATH: &str = "keystore";
 
 /// The file name of the node's Ed25519 secret key inside the chain-specific
 /// network config directory.
-const NODE_KEY_ED25519_FILE: &str = "secret_ed25519";
+pub(crate) const NODE_KEY_ED25519_FILE: &str = "secret_ed25519";
+
+/// Prefix of the `start_node` error when the node could not start because of its database,
+/// the frontend offers `check_node_db` and `purge_chain` when it sees this prefix.
+pub(crate) const NODE_DB_ERROR_PREFIX: &str = "Node database error";
 
 /// The recommended open file descriptor limit to be configured for the process.
 const RECOMMENDED_OPEN_FILE_DESCRIPTOR_LIMIT: u64 = 10_000;
 
 pub(crate) struct ExecutorDispatch;
 
+pub(crate) struct ExecutionDispatch;
+
+#[derive(Clone)]
+struct StartParams {
+    path: String,
+    node_name: String,
+    keystore_password: Option<String>,
+    app_handle: tauri::AppHandle,
+}
+
+/// Health of the node instance, as seen by the watchdog
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub(crate) enum NodeHealth {
+    /// the node was never started, or was stopped on purpose
+    Stopped,
+    /// the task manager of the node exited on its own
+    Exited,
+    Running {
+        #[serde(rename = "bestNumber")]
+        best_number: u32,
+        peers: usize,
+        /// `None` when the executor is not enabled
+        executor: Option<ExecutorHealth>,
+    },
+}
+
+/// Bytes received and sent by the node since it started
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NodeTraffic {
+    total_downloaded_bytes: u64,
+    total_uploaded_bytes: u64,
+}
+
+/// Network throughput of the node
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NodeThroughput {
+    /// average since the previous `get_node_throughput`
+    download_bytes_per_sec: u64,
+    /// average since the previous `get_node_throughput`
+    upload_bytes_per_sec: u64,
+    #[serde(flatten)]
+    traffic: NodeTraffic,
+}
+
+/// Sync state of the executor node
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExecutorHealth {
+    best_number: u32,
+    peers: usize,
+}
+
 impl NativeExecutionDispatch for ExecutorDispatch {
     type ExtendHostFunctions = ();
 
@@ -56,51 +171,111 @@ impl NativeExecutionDispatch for ExecutorDispatch {
     }
 }
 
+impl NativeExecutionDispatch for ExecutionDispatch {
+    type ExtendHostFunctions = ();
+
+    fn dispatch(method: &str, data: &[u8]) -> Option<Vec<u8>> {
+        cirrus_runtime::api::dispatch(method, data)
+    }
+
+    fn native_version() -> sc_executor::NativeVersion {
+        cirrus_runtime::native_version()
+    }
+}
+
 /// starts a new node instance via calling `init_node()`
 /// if there is a node instance running previously,
 /// first it stops the previous instance, then starts a new instance
+/// `keystore_password` is only used when the filesystem keystore is enabled in the node config
 #[tauri::command]
-pub(crate) async fn start_node(path: String, node_name: String) -> Result<String, String> {
-    type FullClient<RuntimeApi, ExecutorDispatch> =
-        sc_service::TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<ExecutorDispatch>>;
+pub(crate) async fn start_node(
+    path: String,
+    node_name: String,
+    keystore_password: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let start_params = StartParams {
+        path,
+        node_name,
+        keystore_password,
+        app_handle,
+    };
+    START_PARAMS.lock().await.replace(start_params.clone());
+
+    start(start_params).await
+}
 
-    static NODE_HANDLE: Mutex<Option<JoinHandle<()>>> = Mutex::const_new(None);
-    static WEAK_CLIENT: Mutex<Option<Weak<FullClient<RuntimeApi, ExecutorDispatch>>>> =
-        Mutex::const_new(None);
+/// restarts the node with the parameters of the last `start_node`
+///
+/// returns `None` if the node was never started or was stopped with `stop_node`
+pub(crate) async fn restart_node() -> Option<Result<String, String>> {
+    let start_params = START_PARAMS.lock().await.clone()?;
+
+    Some(start(start_params).await)
+}
+
+async fn start(start_params: StartParams) -> Result<String, String> {
+    let StartParams {
+        path,
+        node_name,
+        keystore_password,
+        app_handle,
+    } = start_params;
+
+    let node_config = get_node_config(app_handle.clone())?;
+    let performance_settings = get_performance_config(app_handle)?.settings();
+
+    // the node would start with a wrong password, but could not use any of the stored keys
+    if node_config.keystore == KeystoreKind::Filesystem {
+        let keystore_path =
+            keystore_path(path.clone().into()).map_err(|error| error.to_string())?;
+        open_keystore(keystore_path, keystore_password.clone())?;
+    }
 
     let mut node_handle_guard = NODE_HANDLE.lock().await;
     let mut weak_client_guard = WEAK_CLIENT.lock().await;
 
     // if there is already a node running, stop it
-    if let Some(handle) = node_handle_guard.take() {
-        handle.abort();
-        while !handle.is_finished() {
-            sleep(Duration::from_millis(200)).await;
-        }
-        let weak_client = weak_client_guard
-            .take()
-            .expect("previous instance is present");
-        while weak_client.upgrade().is_some() {
-            sleep(Duration::from_millis(200)).await;
-        }
-    }
+    stop_instance(&mut node_handle_guard, &mut weak_client_guard).await;
+
+    // on-disk data must be up to date before the node touches it
+    let migrations_base_directory = PathBuf::from(&path);
+    tokio::task::spawn_blocking(move || run_pending_migrations(&migrations_base_directory))
+        .await
+        .map_err(|error| format!("data migration task failed: {error}"))?
+        .map_err(|error| {
+            error!("Data migration failed: {error:?}");
+            format!("Could not migrate the node data: {error:#}")
+        })?;
 
     // wait for the previous instance to finish if new instance is unable to start
     // TODO: remove this when https://github.com/paritytech/substrate/issues/11654 and https://github.com/subspace/subspace/issues/578 are resolved
     // we cannot know the previous node instance is completely finished for now
     // IMPORTANT: don't remove the timeout mechanism, carry it to the other strategy when removing this: either `is_finished` or `Weak<Client>.upgrade()`
+    let mut last_error = None;
     if (timeout(Duration::from_secs(10), async {
         loop {
-            match init_node(path.clone().into(), node_name.clone()).await {
-                Ok((handle, weak_client)) => {
+            match init_node(
+                path.clone().into(),
+                node_name.clone(),
+                node_config.clone(),
+                performance_settings,
+                keystore_password.clone(),
+            )
+            .await
+            {
+                Ok((handle, weak_client, weak_network, weak_executor)) => {
                     *node_handle_guard = Some(handle);
                     *weak_client_guard = Some(weak_client);
+                    WEAK_NETWORK.lock().await.replace(weak_network);
+                    *WEAK_EXECUTOR.lock().await = weak_executor;
                     break;
                 }
                 Err(err) => {
                     error!(
                         "could not start the node with err: {err}, will wait for 200 milliseconds"
                     );
+                    last_error.replace(err);
                     sleep(Duration::from_millis(200)).await;
                 }
             }
@@ -109,6 +284,12 @@ pub(crate) async fn start_node(path: String, node_name: String) -> Result<String
     .await)
         .is_err()
     {
+        // the database of the previous instance stays locked until it terminates, anything else
+        // wrong with the database will not go away by retrying
+        if let Some(error) = last_error.filter(is_database_error) {
+            error!("Node database is unusable: {error:?}");
+            return Err(format!("{NODE_DB_ERROR_PREFIX}: {error}"));
+        }
         error!(
             "Previous instance is not terminating for 10 seconds, unable to start a new instance"
         );
@@ -117,26 +298,247 @@ pub(crate) async fn start_node(path: String, node_name: String) -> Result<String
     Ok("Successfully started the node in the backend".into())
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RuntimeVersionInfo {
+    spec_name: String,
+    impl_name: String,
+    spec_version: u32,
+    impl_version: u32,
+    transaction_version: u32,
+    /// spec version of the runtime compiled into the app, used by the native execution
+    native_spec_version: u32,
+}
+
+/// returns the version of the runtime the running node executes at its best block
+#[tauri::command]
+pub(crate) async fn get_runtime_version() -> Result<RuntimeVersionInfo, String> {
+    let client = WEAK_CLIENT
+        .lock()
+        .await
+        .as_ref()
+        .and_then(Weak::upgrade)
+        .ok_or_else(|| "The node is not running".to_string())?;
+
+    let best_hash = client.info().best_hash;
+    let version = client
+        .runtime_version_at(&BlockId::Hash(best_hash))
+        .map_err(|error| format!("couldn't get the runtime version because: {error}"))?;
+
+    Ok(RuntimeVersionInfo {
+        spec_name: version.spec_name.to_string(),
+        impl_name: version.impl_name.to_string(),
+        spec_version: version.spec_version,
+        impl_version: version.impl_version,
+        transaction_version: version.transaction_version,
+        native_spec_version: ExecutorDispatch::native_version()
+            .runtime_version
+            .spec_version,
+    })
+}
+
+/// returns the network throughput of the running node, `None` if the node is not running
+#[tauri::command]
+pub(crate) async fn get_node_throughput() -> Option<NodeThroughput> {
+    let traffic = node_traffic().await?;
+    let now = Instant::now();
+
+    let previous_sample = LAST_THROUGHPUT_SAMPLE.lock().await.replace((now, traffic));
+    let (download_bytes_per_sec, upload_bytes_per_sec) = match previous_sample {
+        // the counters start from 0 again when the node is restarted
+        Some((time, previous))
+            if traffic.total_downloaded_bytes >= previous.total_downloaded_bytes
+                && traffic.total_uploaded_bytes >= previous.total_uploaded_bytes =>
+        {
+            let elapsed = now.duration_since(time).as_secs_f64();
+            if elapsed > 0.0 {
+                (
+                    ((traffic.total_downloaded_bytes - previous.total_downloaded_bytes) as f64
+                        / elapsed) as u64,
+                    ((traffic.total_uploaded_bytes - previous.total_uploaded_bytes) as f64
+                        / elapsed) as u64,
+                )
+            } else {
+                (0, 0)
+            }
+        }
+        _ => (0, 0),
+    };
+
+    Some(NodeThroughput {
+        download_bytes_per_sec,
+        upload_bytes_per_sec,
+        traffic,
+    })
+}
+
+/// returns the bytes received and sent by the running node, `None` if the node is not running
+pub(crate) async fn node_traffic() -> Option<NodeTraffic> {
+    let network = WEAK_NETWORK.lock().await.as_ref().and_then(Weak::upgrade)?;
+    // fails when the network worker of the node is gone, the node is stopping
+    let status = network.status().await.ok()?;
+
+    Some(NodeTraffic {
+        total_downloaded_bytes: status.total_bytes_inbound,
+        total_uploaded_bytes: status.total_bytes_outbound,
+    })
+}
+
+/// returns the health of the node instance, without waiting for a node that is starting
+pub(crate) async fn node_health() -> NodeHealth {
+    let node_handle_guard = match NODE_HANDLE.try_lock() {
+        Ok(guard) => guard,
+        // the node is being started or stopped, its health is unknown yet
+        Err(_) => return NodeHealth::Stopped,
+    };
+
+    match node_handle_guard.as_ref() {
+        None => NodeHealth::Stopped,
+        Some(handle) if handle.is_finished() => NodeHealth::Exited,
+        Some(_) => {
+            let best_number = WEAK_CLIENT
+                .lock()
+                .await
+                .as_ref()
+                .and_then(Weak::upgrade)
+                .map(|client| client.info().best_number)
+                .unwrap_or_default();
+            let peers = WEAK_NETWORK
+                .lock()
+                .await
+                .as_ref()
+                .and_then(Weak::upgrade)
+                .map(|network| network.num_connected_peers())
+                .unwrap_or_default();
+
+            let executor =
+                WEAK_EXECUTOR
+                    .lock()
+                    .await
+                    .as_ref()
+                    .and_then(|(weak_client, weak_network)| {
+                        Some(ExecutorHealth {
+                            best_number: weak_client.upgrade()?.info().best_number,
+                            peers: weak_network.upgrade()?.num_connected_peers(),
+                        })
+                    });
+
+            NodeHealth::Running {
+                best_number,
+                peers,
+                executor,
+            }
+        }
+    }
+}
+
+/// stops the running node instance, if there is any
+#[tauri::command]
+pub(crate) async fn stop_node() -> Result<(), String> {
+    // the watchdog must not bring the node back
+    START_PARAMS.lock().await.take();
+
+    let mut node_handle_guard = NODE_HANDLE.lock().await;
+    let mut weak_client_guard = WEAK_CLIENT.lock().await;
+
+    stop_instance(&mut node_handle_guard, &mut weak_client_guard).await;
+    Ok(())
+}
+
+/// waits until the node instance is finished and its client is dropped
+async fn stop_instance(
+    node_handle: &mut Option<JoinHandle<()>>,
+    weak_client: &mut Option<Weak<FullClient<RuntimeApi, ExecutorDispatch>>>,
+) {
+    if let Some(handle) = node_handle.take() {
+        handle.abort();
+        while !handle.is_finished() {
+            sleep(Duration::from_millis(200)).await;
+        }
+        let weak_client = weak_client.take().expect("previous instance is present");
+        while weak_client.upgrade().is_some() {
+            sleep(Duration::from_millis(200)).await;
+        }
+        // the executor runs under the task manager of the node, its database is released last
+        if let Some((weak_executor_client, _)) = WEAK_EXECUTOR.lock().await.take() {
+            while weak_executor_client.upgrade().is_some() {
+                sleep(Duration::from_millis(200)).await;
+            }
+        }
+    }
+}
+
+/// whether the node was started, `None` if the node instance is locked
+pub(crate) fn node_running() -> Option<bool> {
+    NODE_HANDLE
+        .try_lock()
+        .ok()
+        .map(|node_handle| node_handle.is_some())
+}
+
+/// locks the node instance for an operation that needs exclusive access to the node database
+///
+/// the node cannot be started while the returned guard is alive
+pub(crate) async fn lock_stopped_node(
+) -> Result<MutexGuard<'static, Option<JoinHandle<()>>>, String> {
+    let node_handle_guard = NODE_HANDLE.lock().await;
+    let client_alive = WEAK_CLIENT
+        .lock()
+        .await
+        .as_ref()
+        .map_or(false, |weak_client| weak_client.upgrade().is_some());
+
+    if node_handle_guard.is_some() || client_alive {
+        return Err("The node is running, stop it first".into());
+    }
+    Ok(node_handle_guard)
+}
+
+/// creates a node configuration for using the node database without running the node
+pub(crate) fn offline_configuration(base_directory: PathBuf) -> Result<Configuration> {
+    let config = create_configuration(
+        BasePath::Permanenent(base_directory),
+        load_chain_spec()?,
+        Handle::current(),
+        "subspace-desktop".to_string(),
+        NodeConfig::default(),
+        PerformanceConfig::default().settings(),
+        None,
+    )?;
+    Ok(config.base)
+}
+
 async fn init_node(
     base_directory: PathBuf,
     node_name: String,
+    node_config: NodeConfig,
+    performance_settings: PerformanceSettings,
+    keystore_password: Option<String>,
 ) -> Result<(
     JoinHandle<()>,
     Weak<sc_service::TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<ExecutorDispatch>>>,
+    Weak<NodeNetwork>,
+    Option<WeakExecutor>,
 )> {
-    let chain_spec: ConsensusChainSpec<ConsensusGenesisConfig, ExecutionGenesisConfig> =
-        ConsensusChainSpec::from_json_bytes(include_bytes!("../chain-spec.json").as_ref())
-            .map_err(anyhow::Error::msg)?;
+    let chain_spec = load_chain_spec()?;
 
     let full_client_fut = tokio::task::spawn_blocking(move || {
-        Handle::current().block_on(create_full_client(chain_spec, base_directory, node_name))
+        Handle::current().block_on(create_full_client(
+            chain_spec,
+            base_directory,
+            node_name,
+            node_config,
+            performance_settings,
+            keystore_password,
+        ))
     });
-    let mut full_client = full_client_fut.await??;
+    let (mut full_client, weak_executor) = full_client_fut.await??;
 
     full_client.network_starter.start_network();
 
     let client = full_client.client;
     let weak = Arc::downgrade(&client);
+    let weak_network = Arc::downgrade(&full_client.network);
 
     let node_handle = tokio::spawn(async move {
         if let Err(error) = full_client.task_manager.future().await {
@@ -146,7 +548,95 @@ async fn init_node(
         }
     });
 
-    Ok((node_handle, weak))
+    Ok((node_handle, weak, weak_network, weak_executor))
+}
+
+fn load_chain_spec() -> Result<ConsensusChainSpec<ConsensusGenesisConfig, ExecutionGenesisConfig>> {
+    ConsensusChainSpec::from_json_bytes(include_bytes!("../chain-spec.json").as_ref())
+        .map_err(anyhow::Error::msg)
+}
+
+/// returns the chain-specific config directory of the node under the given base directory
+pub(crate) fn chain_config_dir(base_directory: PathBuf) -> Result<PathBuf> {
+    let chain_spec = load_chain_spec()?;
+    Ok(BasePath::Permanenent(base_directory).config_dir(chain_spec.id()))
+}
+
+/// returns the spec of the execution chain, embedded in the spec of the primary chain
+fn execution_chain_spec<CS: ChainSpec>(
+    chain_spec: &CS,
+) -> Result<ExecutionChainSpec<ExecutionGenesisConfig>> {
+    chain_spec
+        .extensions()
+        .get_any(TypeId::of::<ExecutionChainSpec<ExecutionGenesisConfig>>())
+        .downcast_ref()
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("The chain spec has no execution chain spec"))
+}
+
+/// returns the location of the chain database under the given base directory
+pub(crate) fn database_path(base_directory: PathBuf) -> Result<PathBuf> {
+    Ok(chain_config_dir(base_directory)?.join(DEFAULT_DATABASE_PATH))
+}
+
+/// whether the node failed to start because of its database, other than being still in use
+fn is_database_error(error: &anyhow::Error) -> bool {
+    let error = format!("{error:?}").to_lowercase();
+    let database_related = ["database", "paritydb", "parity-db", "corrupt"]
+        .iter()
+        .any(|pattern| error.contains(pattern));
+    let in_use = ["in use", "locked", "temporarily unavailable"]
+        .iter()
+        .any(|pattern| error.contains(pattern));
+
+    database_related && !in_use
+}
+
+/// returns the location of the node's network key under the given base directory
+pub(crate) fn node_key_path(base_directory: PathBuf) -> Result<PathBuf> {
+    Ok(chain_config_dir(base_directory)?
+        .join(DEFAULT_NETWORK_CONFIG_PATH)
+        .join(NODE_KEY_ED25519_FILE))
+}
+
+/// returns the addresses the node listens on for peer connections
+pub(crate) fn listen_addresses() -> Vec<Multiaddr> {
+    vec![
+        "/ip6/::/tcp/30333".parse().expect("Multiaddr is correct"),
+        "/ip4/0.0.0.0/tcp/30333"
+            .parse()
+            .expect("Multiaddr is correct"),
+    ]
+}
+
+/// returns the addresses the executor node listens on for peer connections
+fn executor_listen_addresses() -> Vec<Multiaddr> {
+    vec![
+        "/ip6/::/tcp/30334".parse().expect("Multiaddr is correct"),
+        "/ip4/0.0.0.0/tcp/30334"
+            .parse()
+            .expect("Multiaddr is correct"),
+    ]
+}
+
+/// returns the addresses the running node listens on and the external addresses it found out
+/// about, `None` if the node is not running
+pub(crate) async fn network_addresses() -> Option<Vec<Multiaddr>> {
+    let network = WEAK_NETWORK.lock().await.as_ref().and_then(Weak::upgrade)?;
+    let network_state = network.network_state().await.ok()?;
+
+    Some(
+        network_state
+            .listened_addresses
+            .into_iter()
+            .chain(network_state.external_addresses)
+            .collect(),
+    )
+}
+
+/// returns the location of the filesystem keystore under the given base directory
+pub(crate) fn keystore_path(base_directory: PathBuf) -> Result<PathBuf> {
+    Ok(chain_config_dir(base_directory)?.join(DEFAULT_KEYSTORE_PATH))
 }
 
 // TODO: Allow customization of a bunch of these things
@@ -154,12 +644,16 @@ async fn create_full_client<CS: ChainSpec + 'static>(
     chain_spec: CS,
     base_path: PathBuf,
     node_name: String,
-) -> Result<
+    node_config: NodeConfig,
+    performance_settings: PerformanceSettings,
+    keystore_password: Option<String>,
+) -> Result<(
     NewFull<
         FullClient<RuntimeApi, ExecutorDispatch>,
         impl VerifyFraudProof + Clone + Send + Sync + 'static,
     >,
-> {
+    Option<WeakExecutor>,
+)> {
     // This must only be initialized once
     INITIALIZE_SUBSTRATE.call_once(|| {
         dotenv::dotenv().ok();
@@ -170,6 +664,8 @@ async fn create_full_client<CS: ChainSpec + 'static>(
             "https://forum.subspace.network/",
             env!("SUBSTRATE_CLI_IMPL_VERSION"),
         );
+        // the crash report is written before the substrate hook exits the process
+        crash::set_panic_hook();
 
         if let Some(new_limit) = fdlimit::raise_fd_limit() {
             if new_limit < RECOMMENDED_OPEN_FILE_DESCRIPTOR_LIMIT {
@@ -182,19 +678,23 @@ async fn create_full_client<CS: ChainSpec + 'static>(
         }
     });
 
+    let execution_chain_spec = if node_config.executor.enabled {
+        Some(execution_chain_spec(&chain_spec)?)
+    } else {
+        None
+    };
+
     let config = create_configuration(
-        BasePath::Permanenent(base_path),
+        BasePath::Permanenent(base_path.clone()),
         chain_spec,
         Handle::current(),
-        node_name,
+        node_name.clone(),
+        node_config.clone(),
+        performance_settings,
+        keystore_password.clone(),
     )?;
 
-    let config_dir = config
-        .base_path
-        .as_ref()
-        .map(|base_path| base_path.config_dir("subspace_gemini_1b"));
-
-    let primary_chain_node = subspace_service::new_full::<RuntimeApi, ExecutorDispatch>(
+    let mut primary_chain_node = subspace_service::new_full::<RuntimeApi, ExecutorDispatch>(
         config,
         true,
         sc_consensus_slots::SlotProportion::new(2f32 / 3f32),
@@ -204,25 +704,112 @@ async fn create_full_client<CS: ChainSpec + 'static>(
         sc_service::Error::Other(format!("Failed to build a full subspace node: {error:?}"))
     })?;
 
-    if primary_chain_node.client.info().best_number == 33670 {
-        if let Some(config_dir) = config_dir {
-            let workaround_file = config_dir.join("network").join("gemini_1b_workaround");
-            if !workaround_file.exists() {
-                let _ = std::fs::write(workaround_file, &[]);
-                let _ = std::fs::remove_file(
-                    config_dir
-                        .join(DEFAULT_NETWORK_CONFIG_PATH)
-                        .join(NODE_KEY_ED25519_FILE),
-                );
-                return Err(anyhow!(
-                    "Applied workaround for upgrade from gemini-1b-2022-jun-08, \
-                                    please restart this node"
-                ));
-            }
+    let weak_executor = match execution_chain_spec {
+        Some(execution_chain_spec) => {
+            let executor_config = create_executor_configuration(
+                base_path.join(EXECUTOR_BASE_PATH),
+                execution_chain_spec,
+                node_name,
+                node_config,
+                performance_settings,
+                keystore_password,
+            )?;
+            Some(create_executor(&mut primary_chain_node, executor_config).await?)
         }
-    }
+        None => None,
+    };
+
+    Ok((primary_chain_node, weak_executor))
+}
+
+/// builds the executor node of the execution chain on top of the primary chain node, its tasks
+/// run under the task manager of the primary chain node
+async fn create_executor<Verifier>(
+    primary_chain_node: &mut NewFull<FullClient<RuntimeApi, ExecutorDispatch>, Verifier>,
+    executor_config: Configuration,
+) -> Result<WeakExecutor>
+where
+    Verifier: VerifyFraudProof + Clone + Send + Sync + 'static,
+{
+    let imported_block_notification_stream = primary_chain_node
+        .imported_block_notification_stream
+        .subscribe()
+        .then(|(block_number, _)| async move { block_number });
+    let new_slot_notification_stream = primary_chain_node
+        .new_slot_notification_stream
+        .subscribe()
+        .then(|slot_notification| async move {
+            (
+                slot_notification.new_slot_info.slot,
+                slot_notification.new_slot_info.global_challenge,
+            )
+        });
+
+    let executor = cirrus_node::service::new_full::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        cirrus_runtime::RuntimeApi,
+        ExecutionDispatch,
+    >(
+        executor_config,
+        primary_chain_node.client.clone(),
+        primary_chain_node.network.clone(),
+        &primary_chain_node.select_chain,
+        imported_block_notification_stream,
+        new_slot_notification_stream,
+    )
+    .await
+    .map_err(|error| {
+        sc_service::Error::Other(format!("Failed to build the executor node: {error:?}"))
+    })?;
+
+    let weak_executor = (
+        Arc::downgrade(&executor.client),
+        Arc::downgrade(&executor.network),
+    );
+    primary_chain_node
+        .task_manager
+        .add_child(executor.task_manager);
+    executor.network_starter.start_network();
 
-    Ok(primary_chain_node)
+    Ok(weak_executor)
+}
+
+/// Create a Configuration object for the executor node, the peer settings of the node config
+/// are for the primary chain and are not applied
+fn create_executor_configuration(
+    base_path: PathBuf,
+    chain_spec: ExecutionChainSpec<ExecutionGenesisConfig>,
+    node_name: String,
+    node_config: NodeConfig,
+    performance_settings: PerformanceSettings,
+    keystore_password: Option<String>,
+) -> Result<Configuration> {
+    let rpc_ws = node_config.rpc.executor_ws_address();
+    let mut config = create_configuration(
+        BasePath::Permanenent(base_path),
+        chain_spec,
+        Handle::current(),
+        node_name,
+        node_config,
+        performance_settings,
+        keystore_password,
+    )?
+    .base;
+
+    let network = &mut config.network;
+    network.listen_addresses = executor_listen_addresses();
+    network.boot_nodes = config.chain_spec.boot_nodes().to_vec();
+    network.public_addresses.clear();
+    network.default_peers_set.reserved_nodes.clear();
+    network.default_peers_set.non_reserved_mode = NonReservedPeerMode::Accept;
+    config.rpc_http = None;
+    config.rpc_ws = Some(rpc_ws);
+
+    Ok(config)
 }
 
 fn set_default_ss58_version<CS: ChainSpec>(chain_spec: &CS) {
@@ -250,6 +837,9 @@ fn create_configuration<CS: ChainSpec + 'static>(
     chain_spec: CS,
     tokio_handle: tokio::runtime::Handle,
     node_name: String,
+    node_config: NodeConfig,
+    performance_settings: PerformanceSettings,
+    keystore_password: Option<String>,
 ) -> Result<SubspaceConfiguration> {
     let impl_name = "Subspace-desktop".to_string();
     let impl_version = env!("SUBSTRATE_CLI_IMPL_VERSION").to_string();
@@ -262,21 +852,42 @@ fn create_configuration<CS: ChainSpec + 'static>(
         NodeKeyConfig::Ed25519(Secret::File(net_config_dir.join(NODE_KEY_ED25519_FILE))),
         Some(net_config_dir),
     );
-    network.listen_addresses = vec![
-        "/ip6/::/tcp/30333".parse().expect("Multiaddr is correct"),
-        "/ip4/0.0.0.0/tcp/30333"
-            .parse()
-            .expect("Multiaddr is correct"),
-    ];
+    network.listen_addresses = listen_addresses();
     network.boot_nodes = chain_spec.boot_nodes().to_vec();
 
-    // Increase default value of 25 to improve success rate of sync
-    network.default_peers_set.out_peers = 50;
-    // Full + Light clients
-    network.default_peers_set.in_peers = 25 + 100;
+    // TODO: Upload/download rate limits (with an optional time-of-day schedule) belong here, but
+    //  `NetworkConfiguration` at this Substrate revision has no hook to wrap the libp2p transport
+    //  it builds. The current throughput is reported by `get_node_throughput`.
+    let peers = &node_config.peers;
+    network
+        .boot_nodes
+        .extend(peers.boot_nodes().map_err(anyhow::Error::msg)?);
+    network.public_addresses = peers.public_addresses().map_err(anyhow::Error::msg)?;
+    network.default_peers_set.reserved_nodes =
+        peers.reserved_peers().map_err(anyhow::Error::msg)?;
+    if peers.reserved_only {
+        network.default_peers_set.non_reserved_mode = NonReservedPeerMode::Deny;
+    }
+    network.default_peers_set.out_peers = peers.out_peers;
+    network.default_peers_set.in_peers = peers.in_peers;
     let role = Role::Authority;
-    let (keystore_remote, keystore) = (None, KeystoreConfig::InMemory);
-    let telemetry_endpoints = chain_spec.telemetry_endpoints().clone();
+    let keystore = match node_config.keystore {
+        KeystoreKind::InMemory => KeystoreConfig::InMemory,
+        KeystoreKind::Filesystem => KeystoreConfig::Path {
+            path: config_dir.join(DEFAULT_KEYSTORE_PATH),
+            password: keystore_password.map(SecretString::new),
+        },
+    };
+    let keystore_remote = None;
+    let execution_strategy = node_config.execution.strategy();
+    let rpc = &node_config.rpc;
+    for warning in rpc.warnings() {
+        warn!("{warning}");
+    }
+    let telemetry_endpoints = node_config
+        .telemetry
+        .endpoints(chain_spec.telemetry_endpoints().clone())
+        .map_err(anyhow::Error::msg)?;
 
     // Default value are used for many of parameters
     Ok(SubspaceConfiguration {
@@ -289,9 +900,9 @@ fn create_configuration<CS: ChainSpec + 'static>(
             keystore_remote,
             keystore,
             database: DatabaseSource::ParityDb {
-                path: config_dir.join("paritydb").join("full"),
+                path: config_dir.join(DEFAULT_DATABASE_PATH).join("full"),
             },
-            state_cache_size: 67_108_864,
+            state_cache_size: performance_settings.state_cache_size,
             state_cache_child_ratio: None,
             // TODO: Change to constrained eventually (need DSN for this)
             state_pruning: Some(PruningMode::blocks_pruning(1024)),
@@ -299,34 +910,23 @@ fn create_configuration<CS: ChainSpec + 'static>(
             wasm_method: WasmExecutionMethod::Compiled {
                 instantiation_strategy: WasmtimeInstantiationStrategy::PoolingCopyOnWrite,
             },
-            wasm_runtime_overrides: None,
+            wasm_runtime_overrides: node_config.execution.runtime_overrides.clone(),
             execution_strategies: ExecutionStrategies {
-                syncing: ExecutionStrategy::AlwaysWasm,
-                importing: ExecutionStrategy::AlwaysWasm,
-                block_construction: ExecutionStrategy::AlwaysWasm,
-                offchain_worker: ExecutionStrategy::AlwaysWasm,
-                other: ExecutionStrategy::AlwaysWasm,
+                syncing: execution_strategy,
+                importing: execution_strategy,
+                block_construction: execution_strategy,
+                offchain_worker: execution_strategy,
+                other: execution_strategy,
             },
-            rpc_http: None,
-            rpc_ws: Some("127.0.0.1:9947".parse().expect("IP and port are valid")),
+            rpc_http: rpc.http_address(),
+            rpc_ws: Some(rpc.ws_address()),
             rpc_ipc: None,
-            // necessary in order to use `peers` method to show number of node peers during sync
-            rpc_methods: RpcMethods::Unsafe,
-            rpc_ws_max_connections: Default::default(),
-            // Below CORS are default from Substrate
-            rpc_cors: Some(vec![
-                "http://localhost:*".to_string(),
-                "http://127.0.0.1:*".to_string(),
-                "https://localhost:*".to_string(),
-                "https://127.0.0.1:*".to_string(),
-                "https://polkadot.js.org".to_string(),
-                "tauri://localhost".to_string(),
-                "https://tauri.localhost".to_string(),
-                "http://localhost:3009".to_string(),
-            ]),
-            rpc_max_payload: None,
-            rpc_max_request_size: None,
-            rpc_max_response_size: None,
+            rpc_methods: rpc.methods(),
+            rpc_ws_max_connections: rpc.max_connections,
+            rpc_cors: Some(rpc.cors()),
+            rpc_max_payload: rpc.max_payload,
+            rpc_max_request_size: rpc.max_request_size,
+            rpc_max_response_size: rpc.max_response_size,
             rpc_id_provider: None,
             ws_max_out_buffer_capacity: None,
             prometheus_config: None,
@@ -341,15 +941,16 @@ fn create_configuration<CS: ChainSpec + 'static>(
             tracing_targets: None,
             tracing_receiver: TracingReceiver::Log,
             chain_spec: Box::new(chain_spec),
-            max_runtime_instances: 8,
+            max_runtime_instances: performance_settings.max_runtime_instances,
             announce_block: true,
             role,
             base_path: Some(base_path),
             informant_output_format: Default::default(),
-            runtime_cache_size: 2,
+            runtime_cache_size: performance_settings.runtime_cache_size,
             rpc_max_subs_per_conn: None,
         },
-        force_new_slot_notifications: false,
+        // the executor follows the slots of the primary chain
+        force_new_slot_notifications: node_config.executor.enabled,
         dsn_config: None,
     })
 }
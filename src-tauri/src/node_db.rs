@@ -0,0 +1,290 @@
+use crate::node::{database_path, lock_stopped_node, offline_configuration, ExecutorDispatch};
+use anyhow::Result;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+use subspace_runtime::RuntimeApi;
+use tauri::Manager;
+use tokio::runtime::Handle;
+use tokio::time::Duration;
+use tracing::{error, info, warn};
+
+/// Event emitted to the frontend while blocks are exported.
+const EXPORT_PROGRESS_EVENT: &str = "export_blocks_progress";
+
+/// Event emitted to the frontend while blocks are imported.
+const IMPORT_PROGRESS_EVENT: &str = "import_blocks_progress";
+
+/// Minimum interval between two progress events.
+const PROGRESS_EVENT_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Serialize)]
+pub(crate) struct NodeDbReport {
+    /// `false` if there is no database yet, for example before the first start
+    exists: bool,
+    /// `true` if the database could be opened and its write-ahead log replayed
+    healthy: bool,
+    error: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BlocksProgress {
+    processed_bytes: u64,
+    /// only known for imports, as the size of the input file
+    total_bytes: Option<u64>,
+}
+
+/// opens the node database under `path` read-only and reports whether it is corrupted
+///
+/// the node must be stopped
+#[tauri::command]
+pub(crate) async fn check_node_db(path: String) -> Result<NodeDbReport, String> {
+    let _node_handle_guard = lock_stopped_node().await?;
+
+    let db_path = database_path(path.into())
+        .map_err(|error| error.to_string())?
+        .join("full");
+
+    tokio::task::spawn_blocking(move || check_parity_db(db_path))
+        .await
+        .map_err(|error| format!("database check task failed: {error}"))
+}
+
+/// deletes the chain database under `path`, the node key, the keystore and the config are kept
+///
+/// the node must be stopped, it syncs from scratch on the next start
+#[tauri::command]
+pub(crate) async fn purge_chain(path: String) -> Result<(), String> {
+    let _node_handle_guard = lock_stopped_node().await?;
+
+    let db_path = database_path(path.into()).map_err(|error| error.to_string())?;
+    if !db_path.exists() {
+        return Ok(());
+    }
+
+    warn!("Purging the chain database at {}", db_path.display());
+    match fs::remove_dir_all(db_path) {
+        Ok(_) => Ok(()),
+        Err(why) => Err(format!("couldn't remove the chain database because: {why}")),
+    }
+}
+
+fn check_parity_db(db_path: PathBuf) -> NodeDbReport {
+    let metadata = match parity_db::Options::load_metadata(&db_path) {
+        Ok(Some(metadata)) => metadata,
+        Ok(None) => {
+            return NodeDbReport {
+                exists: false,
+                healthy: true,
+                error: None,
+            }
+        }
+        Err(error) => {
+            return NodeDbReport {
+                exists: true,
+                healthy: false,
+                error: Some(format!("unreadable database metadata: {error}")),
+            }
+        }
+    };
+
+    // use the column options the node created the database with
+    let mut options = parity_db::Options::with_columns(&db_path, metadata.columns.len() as u8);
+    options.columns = metadata.columns;
+    options.salt = Some(metadata.salt);
+
+    // opening replays the write-ahead log, which fails on corrupted tables or logs
+    match parity_db::Db::open_read_only(&options) {
+        Ok(_db) => NodeDbReport {
+            exists: true,
+            healthy: true,
+            error: None,
+        },
+        Err(error) => {
+            error!("Node database check failed: {error}");
+            NodeDbReport {
+                exists: true,
+                healthy: false,
+                error: Some(error.to_string()),
+            }
+        }
+    }
+}
+
+/// exports the blocks of the node database under `path` into the `destination` file
+///
+/// the node must be stopped, `to` defaults to the best block and `binary` selects the
+/// SCALE-encoded block stream instead of JSON
+#[tauri::command]
+pub(crate) async fn export_blocks(
+    path: String,
+    destination: String,
+    from: Option<u32>,
+    to: Option<u32>,
+    binary: bool,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let _node_handle_guard = lock_stopped_node().await?;
+
+    let export_fut = tokio::task::spawn_blocking(move || {
+        Handle::current().block_on(async move {
+            let config = offline_configuration(path.into())?;
+            let partial_components =
+                subspace_service::new_partial::<RuntimeApi, ExecutorDispatch>(&config)?;
+
+            let output = ProgressTracker::new(
+                File::create(&destination)?,
+                app_handle,
+                EXPORT_PROGRESS_EVENT,
+                None,
+            );
+
+            info!("Exporting blocks into {destination}");
+            sc_service::chain_ops::export_blocks(
+                partial_components.client,
+                output,
+                from.unwrap_or(1),
+                to,
+                binary,
+            )
+            .await?;
+
+            Ok::<_, anyhow::Error>(())
+        })
+    });
+
+    match export_fut.await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(error)) => {
+            error!("Failed to export blocks: {error}");
+            Err(format!("couldn't export blocks because: {error}"))
+        }
+        Err(error) => Err(format!("block export task failed: {error}")),
+    }
+}
+
+/// imports the blocks from the `source` file into the node database under `path`
+///
+/// meant to be used on a fresh base path, to skip syncing from genesis, the node must be stopped
+#[tauri::command]
+pub(crate) async fn import_blocks(
+    path: String,
+    source: String,
+    binary: bool,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let _node_handle_guard = lock_stopped_node().await?;
+
+    let import_fut = tokio::task::spawn_blocking(move || {
+        Handle::current().block_on(async move {
+            let config = offline_configuration(path.into())?;
+            let partial_components =
+                subspace_service::new_partial::<RuntimeApi, ExecutorDispatch>(&config)?;
+
+            let file = File::open(&source)?;
+            let total_bytes = file.metadata()?.len();
+            let input =
+                ProgressTracker::new(file, app_handle, IMPORT_PROGRESS_EVENT, Some(total_bytes));
+
+            info!("Importing blocks from {source}");
+            sc_service::chain_ops::import_blocks(
+                partial_components.client,
+                partial_components.import_queue,
+                input,
+                false,
+                binary,
+            )
+            .await?;
+
+            Ok::<_, anyhow::Error>(())
+        })
+    });
+
+    match import_fut.await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(error)) => {
+            error!("Failed to import blocks: {error}");
+            Err(format!("couldn't import blocks because: {error}"))
+        }
+        Err(error) => Err(format!("block import task failed: {error}")),
+    }
+}
+
+/// wraps a block stream and emits progress events to the frontend as bytes go through it
+struct ProgressTracker<T> {
+    inner: T,
+    app_handle: tauri::AppHandle,
+    event: &'static str,
+    processed_bytes: u64,
+    total_bytes: Option<u64>,
+    last_event: Instant,
+}
+
+impl<T> ProgressTracker<T> {
+    fn new(
+        inner: T,
+        app_handle: tauri::AppHandle,
+        event: &'static str,
+        total_bytes: Option<u64>,
+    ) -> Self {
+        Self {
+            inner,
+            app_handle,
+            event,
+            processed_bytes: 0,
+            total_bytes,
+            last_event: Instant::now(),
+        }
+    }
+
+    fn advance(&mut self, bytes: usize) {
+        self.processed_bytes += bytes as u64;
+        if self.last_event.elapsed() >= PROGRESS_EVENT_INTERVAL {
+            self.last_event = Instant::now();
+            self.emit();
+        }
+    }
+
+    fn emit(&self) {
+        let progress = BlocksProgress {
+            processed_bytes: self.processed_bytes,
+            total_bytes: self.total_bytes,
+        };
+        if let Err(error) = self.app_handle.emit_all(self.event, progress) {
+            error!("Failed to emit {}: {error}", self.event);
+        }
+    }
+}
+
+impl<T: Write> Write for ProgressTracker<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.advance(written);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+        self.emit();
+        Ok(())
+    }
+}
+
+impl<T: Read> Read for ProgressTracker<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.advance(read);
+        Ok(read)
+    }
+}
+
+impl<T: Seek> Seek for ProgressTracker<T> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = self.inner.seek(pos)?;
+        self.processed_bytes = position;
+        Ok(position)
+    }
+}
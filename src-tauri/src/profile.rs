@@ -0,0 +1,370 @@
+use crate::config::{get_config, upgrade_config_object, CONFIG_SCHEMA_VERSION};
+use crate::farmer::SINGLE_DISK_FARM_INFO_FILE;
+use crate::identity::{parse_node_key, write_node_key};
+use crate::node::{lock_stopped_node, node_key_path};
+use crate::utils::{append_archive_entry, read_config, write_config};
+use anyhow::{anyhow, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use tracing::{error, info};
+
+/// Version of the profile archive layout, archives of other versions are rejected.
+const PROFILE_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const CONFIG_ENTRY: &str = "config.json";
+const NODE_KEY_ENTRY: &str = "node/secret_ed25519";
+const FARMED_BLOCKS_ENTRY: &str = "farmed-blocks.json";
+
+/// Directory of the farmer files inside the archive, they are stored relative to the plot location.
+const FARMER_ENTRY_PREFIX: &str = "farmer";
+
+/// Files making up the identity of the farmer, found in the plot location or in one of its
+/// sub directories (one per plot).
+const FARMER_IDENTITY_FILES: &[&str] = &[SINGLE_DISK_FARM_INFO_FILE, "identity.bin"];
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileManifest {
+    format_version: u32,
+    /// version of the app the profile was exported with
+    app_version: String,
+    config_schema_version: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ImportedProfile {
+    /// farmed blocks history as exported, for the frontend to store again
+    farmed_blocks: Option<String>,
+    node_key: bool,
+    farmer_identity: bool,
+}
+
+/// writes the config into the `destination` archive, optionally with the node key, the farmer
+/// identity and the farmed blocks history kept by the frontend
+#[tauri::command]
+pub(crate) async fn export_profile(
+    destination: String,
+    include_node_key: bool,
+    include_farmer_identity: bool,
+    farmed_blocks: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let content = read_config(app_handle.clone())?;
+    let plot_location = get_config(app_handle)?.plot.location;
+
+    let profile_destination = destination.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        write_profile(
+            Path::new(&profile_destination),
+            &content,
+            &plot_location,
+            include_node_key,
+            include_farmer_identity,
+            farmed_blocks,
+        )
+    })
+    .await
+    .map_err(|error| format!("profile export task failed: {error}"))?;
+    match result {
+        Ok(()) => {
+            info!("Exported the profile into {destination}");
+            Ok(())
+        }
+        Err(error) => {
+            error!("Failed to export the profile: {error}");
+            Err(format!("couldn't export the profile because: {error}"))
+        }
+    }
+}
+
+/// restores a profile archive from `source`, with its paths remapped to `plot_location`
+///
+/// the node must be stopped and there must be no farm at `plot_location` yet
+#[tauri::command]
+pub(crate) async fn import_profile(
+    source: String,
+    plot_location: String,
+    app_handle: tauri::AppHandle,
+) -> Result<ImportedProfile, String> {
+    let _node_handle_guard = lock_stopped_node().await?;
+
+    let profile_source = source.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        read_profile(Path::new(&profile_source))
+            .and_then(|entries| restore_profile(entries, Path::new(&plot_location), app_handle))
+    })
+    .await
+    .map_err(|error| format!("profile import task failed: {error}"))?;
+    match result {
+        Ok(imported) => {
+            info!("Imported the profile from {source}");
+            Ok(imported)
+        }
+        Err(error) => {
+            error!("Failed to import the profile: {error}");
+            Err(format!("couldn't import the profile because: {error}"))
+        }
+    }
+}
+
+fn write_profile(
+    destination: &Path,
+    config: &str,
+    plot_location: &Path,
+    include_node_key: bool,
+    include_farmer_identity: bool,
+    farmed_blocks: Option<String>,
+) -> Result<()> {
+    // a failed export must not leave a truncated archive behind, or replace a previous one
+    let mut file_name = destination.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".tmp");
+    let temp_path = destination.with_file_name(file_name);
+
+    let result = write_profile_archive(
+        &temp_path,
+        config,
+        plot_location,
+        include_node_key,
+        include_farmer_identity,
+        farmed_blocks,
+    )
+    .and_then(|()| Ok(fs::rename(&temp_path, destination)?));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn write_profile_archive(
+    archive_path: &Path,
+    config: &str,
+    plot_location: &Path,
+    include_node_key: bool,
+    include_farmer_identity: bool,
+    farmed_blocks: Option<String>,
+) -> Result<()> {
+    if plot_location.as_os_str().is_empty() {
+        return Err(anyhow!(
+            "there is no plot location yet, finish the setup first"
+        ));
+    }
+
+    let mut archive = tar::Builder::new(GzEncoder::new(
+        File::create(archive_path)?,
+        Compression::default(),
+    ));
+
+    let manifest = ProfileManifest {
+        format_version: PROFILE_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        config_schema_version: CONFIG_SCHEMA_VERSION,
+    };
+    append_archive_entry(
+        &mut archive,
+        MANIFEST_ENTRY,
+        &serde_json::to_vec_pretty(&manifest)?,
+    )?;
+    append_archive_entry(&mut archive, CONFIG_ENTRY, config.as_bytes())?;
+
+    if include_node_key {
+        let key_path = node_key_path(plot_location.to_path_buf())?;
+        if !key_path.exists() {
+            return Err(anyhow!(
+                "there is no node key yet, start the node once to create it"
+            ));
+        }
+        append_archive_entry(&mut archive, NODE_KEY_ENTRY, &fs::read(key_path)?)?;
+    }
+
+    if include_farmer_identity {
+        let identity_files = farmer_identity_files(plot_location)?;
+        if identity_files.is_empty() {
+            return Err(anyhow!("there is no farmer identity in the plot location"));
+        }
+        for relative_path in identity_files {
+            let entry_path = Path::new(FARMER_ENTRY_PREFIX).join(&relative_path);
+            append_archive_entry(
+                &mut archive,
+                entry_path,
+                &fs::read(plot_location.join(relative_path))?,
+            )?;
+        }
+    }
+
+    if let Some(farmed_blocks) = farmed_blocks {
+        append_archive_entry(&mut archive, FARMED_BLOCKS_ENTRY, farmed_blocks.as_bytes())?;
+    }
+
+    archive.into_inner()?.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// returns the farmer identity files, relative to the plot location
+fn farmer_identity_files(plot_location: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    if !plot_location.exists() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(plot_location)?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            for sub_entry in fs::read_dir(&path)?.flatten() {
+                if is_farmer_identity_file(&sub_entry.path()) {
+                    files.push(PathBuf::from(entry.file_name()).join(sub_entry.file_name()));
+                }
+            }
+        } else if is_farmer_identity_file(&path) {
+            files.push(PathBuf::from(entry.file_name()));
+        }
+    }
+
+    Ok(files)
+}
+
+fn is_farmer_identity_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .map_or(false, |file_name| {
+                FARMER_IDENTITY_FILES.contains(&file_name)
+            })
+}
+
+/// reads all the files of the archive, profiles are small
+fn read_profile(source: &Path) -> Result<BTreeMap<PathBuf, Vec<u8>>> {
+    let mut archive = tar::Archive::new(GzDecoder::new(File::open(source)?));
+
+    let mut entries = BTreeMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path()?.into_owned();
+        // never write outside of the target directories
+        if !path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(anyhow!("invalid path in the archive: {}", path.display()));
+        }
+
+        let mut content = vec![];
+        entry.read_to_end(&mut content)?;
+        entries.insert(path, content);
+    }
+
+    Ok(entries)
+}
+
+fn restore_profile(
+    mut entries: BTreeMap<PathBuf, Vec<u8>>,
+    plot_location: &Path,
+    app_handle: tauri::AppHandle,
+) -> Result<ImportedProfile> {
+    if !plot_location.is_absolute() {
+        return Err(anyhow!("plot location must be an absolute path"));
+    }
+
+    let manifest: ProfileManifest = serde_json::from_slice(
+        &entries
+            .remove(Path::new(MANIFEST_ENTRY))
+            .ok_or_else(|| anyhow!("not a profile archive, the manifest is missing"))?,
+    )?;
+    if manifest.format_version != PROFILE_FORMAT_VERSION {
+        return Err(anyhow!(
+            "profile format version {} is not supported by this version of the app",
+            manifest.format_version
+        ));
+    }
+    if manifest.config_schema_version > CONFIG_SCHEMA_VERSION {
+        return Err(anyhow!(
+            "profile was exported by a newer version of the app ({}), update the app first",
+            manifest.app_version
+        ));
+    }
+
+    let mut config: Map<String, Value> = serde_json::from_slice(
+        &entries
+            .remove(Path::new(CONFIG_ENTRY))
+            .ok_or_else(|| anyhow!("the config is missing from the profile"))?,
+    )?;
+    upgrade_config_object(&mut config).map_err(anyhow::Error::msg)?;
+    // the plot size and the other plot settings are kept, only the location changes
+    match config.get_mut("plot").and_then(Value::as_object_mut) {
+        Some(plot) => {
+            plot.insert(
+                "location".to_string(),
+                plot_location.to_string_lossy().into(),
+            );
+        }
+        None => return Err(anyhow!("the config in the profile has no plot settings")),
+    }
+
+    let node_key = entries
+        .remove(Path::new(NODE_KEY_ENTRY))
+        .map(parse_node_key)
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+
+    let farmed_blocks = entries
+        .remove(Path::new(FARMED_BLOCKS_ENTRY))
+        .map(String::from_utf8)
+        .transpose()?;
+
+    let farmer_files = entries
+        .into_iter()
+        .filter_map(|(path, content)| {
+            path.strip_prefix(FARMER_ENTRY_PREFIX)
+                .ok()
+                .map(|relative_path| (relative_path.to_path_buf(), content))
+        })
+        .filter(|(relative_path, _)| {
+            relative_path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .map_or(false, |file_name| {
+                    FARMER_IDENTITY_FILES.contains(&file_name)
+                })
+        })
+        .collect::<Vec<_>>();
+    if !farmer_files.is_empty() && !farmer_identity_files(plot_location)?.is_empty() {
+        return Err(anyhow!(
+            "there is already a farm in the plot location, choose an empty directory"
+        ));
+    }
+
+    for (relative_path, content) in &farmer_files {
+        let path = plot_location.join(relative_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+
+    if let Some(node_key) = &node_key {
+        write_node_key(&node_key_path(plot_location.to_path_buf())?, node_key)
+            .map_err(anyhow::Error::msg)?;
+    }
+
+    // the config goes last, the profile counts as imported once it is written
+    write_config(serde_json::to_string_pretty(&config)?, app_handle).map_err(anyhow::Error::msg)?;
+
+    Ok(ImportedProfile {
+        farmed_blocks,
+        node_key: node_key.is_some(),
+        farmer_identity: !farmer_files.is_empty(),
+    })
+}
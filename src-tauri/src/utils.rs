@@ -1,14 +1,18 @@
+use crate::config::prepare_config;
 use serde::Serialize;
 use std::fs;
-use std::fs::File;
-use std::io::prelude::*;
-use std::path::PathBuf;
+use std::fs::{File, OpenOptions};
+use std::io::{self, prelude::*};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tauri::{api, Env};
-use tracing::{debug, error, info};
+use tracing::{debug, warn};
 
 #[cfg(not(target_os = "windows"))]
-use std::os::unix::fs::PermissionsExt;
+use std::os::unix::fs::OpenOptionsExt;
+
+/// Number of previous configs kept next to the config file.
+const CONFIG_BACKUPS: usize = 3;
 
 #[derive(Serialize)]
 pub(crate) struct DiskStats {
@@ -17,15 +21,17 @@ pub(crate) struct DiskStats {
 }
 
 #[tauri::command]
-pub(crate) fn get_disk_stats(dir: String) -> DiskStats {
+pub(crate) fn get_disk_stats(dir: String) -> Result<DiskStats, String> {
     debug!("{}", dir);
-    let free: u64 = fs2::available_space(&dir).expect("error");
-    let total: u64 = fs2::total_space(&dir).expect("error");
+    let free: u64 = fs2::available_space(&dir)
+        .map_err(|why| format!("couldn't get the free space of {dir} because: {why}"))?;
+    let total: u64 = fs2::total_space(&dir)
+        .map_err(|why| format!("couldn't get the total space of {dir} because: {why}"))?;
 
-    DiskStats {
+    Ok(DiskStats {
         free_bytes: free,
         total_bytes: total,
-    }
+    })
 }
 
 #[tauri::command]
@@ -34,16 +40,6 @@ pub(crate) fn get_this_binary() -> PathBuf {
     bin.unwrap()
 }
 
-#[tauri::command]
-pub(crate) fn frontend_error_logger(message: &str) {
-    error!("Frontend error: {message}");
-}
-
-#[tauri::command]
-pub(crate) fn frontend_info_logger(message: &str) {
-    info!("Frontend info: {message}");
-}
-
 #[tauri::command]
 pub(crate) fn open_log_dir(app_handle: tauri::AppHandle) {
     let path = custom_log_dir(&app_handle.config().tauri.bundle.identifier);
@@ -67,33 +63,81 @@ pub(crate) fn open_log_dir(app_handle: tauri::AppHandle) {
         .expect("could not open the specified directory");
 }
 
+/// rejects configs that do not parse into `AppConfig` or fail its validation
+///
+/// the previous config is kept as a backup and the new one replaces it atomically, so a crash or a
+/// full disk never leaves a truncated config behind
 #[tauri::command]
 pub(crate) fn write_config(content: String, app_handle: tauri::AppHandle) -> Result<(), String> {
     let path = config_file_path(app_handle);
+    let previous = fs::read_to_string(&path).ok();
+    let content = prepare_config(&content, previous.as_deref())?;
+
+    backup_config(&path);
+    match write_atomically(&path, content.as_bytes()) {
+        Ok(_) => Ok(()),
+        Err(why) => Err(format!("couldn't write config because: {why}")),
+    }
+}
 
-    match File::create(path) {
-        Ok(mut file) => {
-            file.write_all(content.as_bytes())
-                .expect("couldn't write file");
-
-            // config file is created under the current user's folder, in Windows, other users do not have read/write access to these
-            #[cfg(not(target_os = "windows"))]
-            {
-                let mut perms = file
-                    .metadata()
-                    .expect("could not get metadata")
-                    .permissions();
-                perms.set_mode(0o600);
-
-                file.set_permissions(perms)
-                    .expect("failed to set permissions for the config");
+/// shifts the rolling backups of the config by one and copies the current config into the first
+fn backup_config(path: &Path) {
+    if !path.exists() {
+        return;
+    }
+
+    let backup_path = |index: usize| {
+        let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(format!(".bak.{index}"));
+        path.with_file_name(file_name)
+    };
+
+    for index in (1..CONFIG_BACKUPS).rev() {
+        let backup = backup_path(index);
+        if backup.exists() {
+            if let Err(error) = fs::rename(&backup, backup_path(index + 1)) {
+                warn!(
+                    "Failed to rotate the config backup {}: {error}",
+                    backup.display()
+                );
             }
-            Ok(())
         }
-        Err(why) => Err(format!("couldn't create config because: {why}")),
+    }
+    if let Err(error) = fs::copy(path, backup_path(1)) {
+        warn!("Failed to back up the config: {error}");
     }
 }
 
+/// writes into a temporary file next to `path`, syncs it to the disk, then renames it over `path`
+pub(crate) fn write_atomically(path: &Path, content: &[u8]) -> io::Result<()> {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".tmp");
+    let temp_path = path.with_file_name(file_name);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // files are created under the current user's folder, in Windows, other users do not have read/write access to these
+    #[cfg(not(target_os = "windows"))]
+    options.mode(0o600);
+
+    let result = options.open(&temp_path).and_then(|mut file| {
+        file.write_all(content)?;
+        file.sync_all()
+    });
+    if let Err(error) = result.and_then(|_| fs::rename(&temp_path, path)) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error);
+    }
+
+    // make the rename itself durable
+    #[cfg(not(target_os = "windows"))]
+    if let Some(parent) = path.parent() {
+        File::open(parent)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 pub(crate) fn read_config(app_handle: tauri::AppHandle) -> Result<String, String> {
     let path = config_file_path(app_handle);
@@ -146,6 +190,19 @@ pub(crate) fn entry_count_directory(path: &str) -> isize {
     }
 }
 
+/// adds a file with the given content to a tar archive, readable by the current user only
+pub(crate) fn append_archive_entry<W: Write>(
+    archive: &mut tar::Builder<W>,
+    path: impl AsRef<Path>,
+    content: &[u8],
+) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    // archives may contain keys
+    header.set_mode(0o600);
+    archive.append_data(&mut header, path, content)
+}
+
 pub(crate) fn custom_log_dir(id: &str) -> PathBuf {
     #[cfg(target_os = "macos")]
     let path = dirs::home_dir().map(|dir| dir.join("Library/Logs").join(id));
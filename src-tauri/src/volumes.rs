@@ -0,0 +1,402 @@
+use crate::farmer::SINGLE_DISK_FARM_INFO_FILE;
+use fs2::FileExt;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+/// Size of the sparse file created to check that the filesystem supports files above 4 GiB.
+const LARGE_FILE_TEST_SIZE: u64 = 4 * 1024 * 1024 * 1024 + 1;
+
+/// Size of the space allocated to check that the filesystem supports preallocation.
+const ALLOCATION_TEST_SIZE: u64 = 1024 * 1024;
+
+/// Name of the file used to probe the plot directory, removed after the checks.
+const PROBE_FILE: &str = ".subspace-desktop-probe";
+
+/// FAT filesystems, their files can not be larger than 4 GiB.
+const FAT_FILE_SYSTEMS: &[&str] = &["vfat", "msdos", "fat", "umsdos"];
+
+/// Filesystems backed by another machine.
+const NETWORK_FILE_SYSTEMS: &[&str] = &[
+    "9p",
+    "afs",
+    "ceph",
+    "cifs",
+    "glusterfs",
+    "ncpfs",
+    "nfs",
+    "nfs4",
+    "smb3",
+    "smbfs",
+];
+
+/// Filesystems without persistent storage behind them, never offered for plots.
+#[cfg(target_os = "linux")]
+const PSEUDO_FILE_SYSTEMS: &[&str] = &[
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "overlay",
+    "proc",
+    "pstore",
+    "ramfs",
+    "rpc_pipefs",
+    "securityfs",
+    "squashfs",
+    "sysfs",
+    "tmpfs",
+    "tracefs",
+];
+
+/// A mounted filesystem the plot could be stored on
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Volume {
+    pub(crate) mount_point: PathBuf,
+    /// filesystem type as reported by the kernel, for example `ext4` or `vfat`
+    pub(crate) file_system: String,
+    /// mount source, usually the device, for example `/dev/sda1`
+    pub(crate) device: String,
+    pub(crate) free_bytes: u64,
+    pub(crate) total_bytes: u64,
+    /// `None` when the kernel does not tell, for example for network filesystems
+    pub(crate) rotational: Option<bool>,
+    pub(crate) read_only: bool,
+}
+
+/// Result of `plot_directory_report`
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct PlotDirectoryReport {
+    /// problems that make plotting fail, the directory must not be used
+    errors: Vec<String>,
+    /// problems that make plotting slow or unreliable, the user may still go on
+    warnings: Vec<String>,
+}
+
+/// checks that the plot can be stored in the directory at `path`, or in the directory that will
+/// be created there
+#[tauri::command]
+pub(crate) async fn check_plot_directory(path: String) -> Result<PlotDirectoryReport, String> {
+    // the probe file is preallocated, which can take a while on slow disks
+    tokio::task::spawn_blocking(move || plot_directory_report(path))
+        .await
+        .map_err(|error| format!("plot directory check task failed: {error}"))
+}
+
+pub(crate) fn plot_directory_report(path: String) -> PlotDirectoryReport {
+    let path = PathBuf::from(path);
+    let mut report = plot_volume_report(&path);
+    // the probes below would fail with a less helpful error, FAT would even write 4 GiB of zeros
+    if !report.errors.is_empty() {
+        return report;
+    }
+
+    if let Some(directory) = path.ancestors().find(|ancestor| ancestor.is_dir()) {
+        if let Err(error) = probe_directory(directory) {
+            report.errors.push(error);
+        }
+    }
+
+    report
+}
+
+/// the checks of `plot_directory_report` that only look at the path and its volume, nothing is
+/// written to the disk
+pub(crate) fn plot_volume_report(path: &Path) -> PlotDirectoryReport {
+    let mut report = PlotDirectoryReport::default();
+
+    if !path.is_absolute() {
+        report
+            .errors
+            .push("plot directory must be an absolute path".into());
+        return report;
+    }
+    let directory = match path.ancestors().find(|ancestor| ancestor.is_dir()) {
+        Some(directory) => directory,
+        None => {
+            report
+                .errors
+                .push("none of the parent directories exist".into());
+            return report;
+        }
+    };
+
+    match volume_of(directory) {
+        Ok(Some(volume)) => check_volume(&volume, &mut report),
+        Ok(None) => {}
+        Err(error) => report.warnings.push(error),
+    }
+
+    if path.join(SINGLE_DISK_FARM_INFO_FILE).exists() {
+        report
+            .warnings
+            .push("the directory already contains a farm, it will be reused".into());
+    }
+
+    report
+}
+
+fn check_volume(volume: &Volume, report: &mut PlotDirectoryReport) {
+    let file_system = volume.file_system.as_str();
+
+    if volume.read_only {
+        report.errors.push(format!(
+            "{} is mounted read-only",
+            volume.mount_point.display()
+        ));
+    }
+    if FAT_FILE_SYSTEMS.contains(&file_system) {
+        report.errors.push(format!(
+            "{file_system} filesystems can not store files larger than 4 GiB, use a disk \
+            formatted with another filesystem"
+        ));
+    }
+    if NETWORK_FILE_SYSTEMS.contains(&file_system) {
+        report.warnings.push(format!(
+            "{file_system} is a network filesystem, plotting and farming over the network is \
+            slow and stops when the connection drops"
+        ));
+    }
+    if file_system == "fuse" || file_system.starts_with("fuse.") || file_system == "fuseblk" {
+        report.warnings.push(format!(
+            "{file_system} is a filesystem in userspace, it is usually much slower than one \
+            supported by the kernel"
+        ));
+    }
+}
+
+/// writes a probe file into `directory`, with preallocated space and a size above the FAT limit
+fn probe_directory(directory: &Path) -> Result<(), String> {
+    let probe_path = directory.join(PROBE_FILE);
+    let result = (|| -> Result<(), String> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&probe_path)
+            .map_err(|why| format!("the directory is not writable: {why}"))?;
+
+        file.allocate(ALLOCATION_TEST_SIZE)
+            .map_err(|why| format!("the filesystem does not support preallocation: {why}"))?;
+
+        // sparse on every filesystem that supports files this large
+        file.set_len(LARGE_FILE_TEST_SIZE)
+            .map_err(|why| format!("the filesystem does not support large files: {why}"))?;
+
+        Ok(())
+    })();
+
+    let _ = fs::remove_file(&probe_path);
+    result
+}
+
+/// lists the mounted volumes, the ones with the most free space first
+#[tauri::command]
+pub(crate) async fn list_volumes() -> Result<Vec<Volume>, String> {
+    // network mounts that stopped responding block `statvfs`
+    tokio::task::spawn_blocking(sorted_volumes)
+        .await
+        .map_err(|error| format!("volume listing task failed: {error}"))?
+}
+
+pub(crate) fn sorted_volumes() -> Result<Vec<Volume>, String> {
+    let mut volumes = mounted_volumes()?;
+    volumes.sort_by(|a, b| b.free_bytes.cmp(&a.free_bytes));
+    Ok(volumes)
+}
+
+/// returns the volume `path` is stored on, `None` if it can not be found out on this platform
+#[cfg(target_os = "linux")]
+fn volume_of(path: &Path) -> Result<Option<Volume>, String> {
+    let path = path
+        .canonicalize()
+        .map_err(|why| format!("couldn't resolve {} because: {why}", path.display()))?;
+
+    // the deepest mount point containing the path
+    Ok(mounted_volumes()?
+        .into_iter()
+        .filter(|volume| path.starts_with(&volume.mount_point))
+        .max_by_key(|volume| volume.mount_point.components().count()))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn volume_of(_path: &Path) -> Result<Option<Volume>, String> {
+    Ok(None)
+}
+
+#[cfg(target_os = "linux")]
+fn mounted_volumes() -> Result<Vec<Volume>, String> {
+    let mountinfo = fs::read_to_string("/proc/self/mountinfo")
+        .map_err(|why| format!("couldn't read the mounted volumes because: {why}"))?;
+
+    let mut volumes: Vec<Volume> = vec![];
+    for mount in mountinfo.lines().filter_map(parse_mountinfo_line) {
+        if PSEUDO_FILE_SYSTEMS.contains(&mount.file_system.as_str()) {
+            continue;
+        }
+        // mounts we are not allowed to look into are of no use for plots either
+        let (free_bytes, total_bytes) = match (
+            fs2::available_space(&mount.mount_point),
+            fs2::total_space(&mount.mount_point),
+        ) {
+            (Ok(free_bytes), Ok(total_bytes)) if total_bytes > 0 => (free_bytes, total_bytes),
+            _ => continue,
+        };
+
+        // a later mount on the same mount point hides the earlier one
+        volumes.retain(|volume| volume.mount_point != mount.mount_point);
+        volumes.push(Volume {
+            rotational: rotational(&mount.device_number),
+            mount_point: mount.mount_point,
+            file_system: mount.file_system,
+            device: mount.source,
+            free_bytes,
+            total_bytes,
+            read_only: mount.read_only,
+        });
+    }
+
+    Ok(volumes)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn mounted_volumes() -> Result<Vec<Volume>, String> {
+    Err("listing volumes is only supported on Linux".into())
+}
+
+#[cfg(target_os = "linux")]
+struct Mount {
+    /// `major:minor`
+    device_number: String,
+    mount_point: PathBuf,
+    read_only: bool,
+    file_system: String,
+    source: String,
+}
+
+/// parses a line of `/proc/self/mountinfo`, see `man 5 proc`:
+///
+/// `36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue`
+#[cfg(target_os = "linux")]
+fn parse_mountinfo_line(line: &str) -> Option<Mount> {
+    let (mount_fields, filesystem_fields) = line.split_once(" - ")?;
+
+    let mut mount_fields = mount_fields.split(' ');
+    let device_number = mount_fields.nth(2)?.to_string();
+    let mount_point = PathBuf::from(unescape_mountinfo(mount_fields.nth(1)?));
+    let read_only = mount_fields.next()?.split(',').any(|option| option == "ro");
+
+    let mut filesystem_fields = filesystem_fields.split(' ');
+    let file_system = filesystem_fields.next()?.to_string();
+    let source = unescape_mountinfo(filesystem_fields.next()?);
+
+    Some(Mount {
+        device_number,
+        mount_point,
+        read_only,
+        file_system,
+        source,
+    })
+}
+
+/// spaces, tabs, new lines and backslashes are escaped as octal, for example `\040`
+#[cfg(target_os = "linux")]
+fn unescape_mountinfo(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(index) = rest.find('\\') {
+        unescaped.push_str(&rest[..index]);
+        let escaped = rest.get(index + 1..index + 4);
+        match escaped.and_then(|octal| u8::from_str_radix(octal, 8).ok()) {
+            Some(byte) => {
+                unescaped.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// partitions have no queue of their own, the one of their disk is used instead
+#[cfg(target_os = "linux")]
+fn rotational(device_number: &str) -> Option<bool> {
+    let device = Path::new("/sys/dev/block").join(device_number);
+    [
+        device.join("queue/rotational"),
+        device.join("../queue/rotational"),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|rotational| rotational.trim() == "1")
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mountinfo_lines() {
+        let mount = parse_mountinfo_line(
+            "36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue",
+        )
+        .unwrap();
+        assert_eq!(mount.device_number, "98:0");
+        assert_eq!(mount.mount_point, Path::new("/mnt2"));
+        assert!(!mount.read_only);
+        assert_eq!(mount.file_system, "ext3");
+        assert_eq!(mount.source, "/dev/root");
+
+        // no optional fields, read-only, escaped spaces in the mount point
+        let mount = parse_mountinfo_line(
+            "97 29 8:17 / /media/user/USB\\040DISK ro,nosuid,nodev - vfat /dev/sdb1 ro,fmask=0022",
+        )
+        .unwrap();
+        assert_eq!(mount.device_number, "8:17");
+        assert_eq!(mount.mount_point, Path::new("/media/user/USB DISK"));
+        assert!(mount.read_only);
+        assert_eq!(mount.file_system, "vfat");
+        assert_eq!(mount.source, "/dev/sdb1");
+
+        // "ro" in the super block options only does not make the mount read-only
+        let mount =
+            parse_mountinfo_line("25 1 0:22 / /sys rw,relatime shared:7 - sysfs sysfs ro").unwrap();
+        assert!(!mount.read_only);
+    }
+
+    #[test]
+    fn rejects_truncated_mountinfo_lines() {
+        assert!(parse_mountinfo_line("").is_none());
+        assert!(parse_mountinfo_line("36 35 98:0 /mnt1 /mnt2 rw,noatime master:1").is_none());
+        assert!(parse_mountinfo_line("36 35 98:0 /mnt1 - ext3 /dev/root rw").is_none());
+        assert!(parse_mountinfo_line("36 35 98:0 /mnt1 /mnt2 rw - ext3").is_none());
+    }
+
+    #[test]
+    fn unescapes_octal_sequences() {
+        assert_eq!(unescape_mountinfo("/mnt/plain"), "/mnt/plain");
+        assert_eq!(unescape_mountinfo("/mnt/a\\040b"), "/mnt/a b");
+        assert_eq!(unescape_mountinfo("\\011tab\\012"), "\ttab\n");
+        assert_eq!(unescape_mountinfo("back\\134slash"), "back\\slash");
+        // not an escape sequence, kept as is
+        assert_eq!(unescape_mountinfo("a\\b"), "a\\b");
+        assert_eq!(unescape_mountinfo("end\\04"), "end\\04");
+        assert_eq!(unescape_mountinfo("end\\"), "end\\");
+    }
+}
@@ -0,0 +1,182 @@
+use crate::config::{get_node_config, WatchdogConfig};
+use crate::farmer;
+use crate::node::{node_health, restart_node, NodeHealth};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tauri::Manager;
+use tokio::time::{sleep, Duration};
+use tracing::{error, info, warn};
+
+/// How often the watchdog checks the node.
+const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Delay before the first restart, doubled after every restart without sync progress.
+const MIN_RESTART_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Upper bound of the delay between restarts.
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// Number of incidents kept for the UI.
+const MAX_INCIDENTS: usize = 100;
+
+/// Event emitted to the frontend for every incident.
+const INCIDENT_EVENT: &str = "node_incident";
+
+static INCIDENTS: Mutex<Vec<Incident>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum IncidentKind {
+    TaskManagerExited,
+    SyncStalled,
+    RestartFailed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Incident {
+    kind: IncidentKind,
+    message: String,
+    /// seconds since the unix epoch
+    timestamp: u64,
+}
+
+/// returns the incidents noticed by the watchdog, oldest first
+#[tauri::command]
+pub(crate) fn get_node_incidents() -> Vec<Incident> {
+    INCIDENTS
+        .lock()
+        .expect("incidents lock is never poisoned")
+        .iter()
+        .cloned()
+        .collect()
+}
+
+/// same as `get_node_incidents` without waiting for the lock, `None` if it is held
+pub(crate) fn try_get_node_incidents() -> Option<Vec<Incident>> {
+    INCIDENTS.try_lock().ok().map(|incidents| incidents.clone())
+}
+
+/// starts watching the node, restarts it (and the farmer) when its task manager exits or when
+/// it stops importing new blocks while peers are connected
+pub(crate) fn spawn(app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        // best block and when it was first seen
+        let mut progress: Option<(u32, Instant)> = None;
+        let mut restarts_without_progress = 0;
+        // the last restart failed, the node is stopped until it is restarted successfully
+        let mut restart_pending = false;
+
+        loop {
+            sleep(CHECK_INTERVAL).await;
+
+            let config = get_node_config(app_handle.clone())
+                .map(|node_config| node_config.watchdog)
+                .unwrap_or_else(|error| {
+                    warn!("Watchdog could not read the node config: {error}");
+                    WatchdogConfig::default()
+                });
+            if !config.enabled {
+                progress = None;
+                continue;
+            }
+            let sync_stall_timeout = Duration::from_secs(config.sync_stall_minutes * 60);
+
+            let incident = match node_health().await {
+                NodeHealth::Stopped if !restart_pending => {
+                    progress = None;
+                    continue;
+                }
+                NodeHealth::Stopped => None,
+                NodeHealth::Exited => Some((
+                    IncidentKind::TaskManagerExited,
+                    "Node task manager exited".to_string(),
+                )),
+                NodeHealth::Running {
+                    best_number, peers, ..
+                } => match progress {
+                    Some((number, since)) if number == best_number && peers > 0 => {
+                        if since.elapsed() < sync_stall_timeout {
+                            continue;
+                        }
+                        Some((
+                            IncidentKind::SyncStalled,
+                            format!(
+                                "No new best block after #{best_number} for {} minutes with {peers} \
+                                peers connected",
+                                config.sync_stall_minutes
+                            ),
+                        ))
+                    }
+                    Some((number, _)) if number == best_number => {
+                        // there is no one to sync from, restart the stall timer
+                        progress = Some((best_number, Instant::now()));
+                        continue;
+                    }
+                    previous => {
+                        if previous.is_some() {
+                            restarts_without_progress = 0;
+                        }
+                        progress = Some((best_number, Instant::now()));
+                        continue;
+                    }
+                },
+            };
+
+            if let Some((kind, message)) = incident {
+                record_incident(&app_handle, kind, message);
+            }
+            progress = None;
+
+            let backoff = MIN_RESTART_BACKOFF
+                .saturating_mul(2u32.saturating_pow(restarts_without_progress))
+                .min(MAX_RESTART_BACKOFF);
+            restarts_without_progress += 1;
+            info!("Restarting the node in {} seconds", backoff.as_secs());
+            sleep(backoff).await;
+
+            match restart_node().await {
+                // stopped on purpose in the meantime
+                None => restart_pending = false,
+                Some(Ok(_)) => {
+                    info!("Node restarted by the watchdog");
+                    restart_pending = false;
+                    farmer::request_restart();
+                }
+                Some(Err(error)) => {
+                    restart_pending = true;
+                    record_incident(
+                        &app_handle,
+                        IncidentKind::RestartFailed,
+                        format!("Could not restart the node: {error}"),
+                    );
+                }
+            }
+        }
+    });
+}
+
+fn record_incident(app_handle: &tauri::AppHandle, kind: IncidentKind, message: String) {
+    error!("Node incident: {message}");
+
+    let incident = Incident {
+        kind,
+        message,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+    };
+
+    {
+        let mut incidents = INCIDENTS.lock().expect("incidents lock is never poisoned");
+        if incidents.len() == MAX_INCIDENTS {
+            incidents.remove(0);
+        }
+        incidents.push(incident.clone());
+    }
+
+    if let Err(error) = app_handle.emit_all(INCIDENT_EVENT, incident) {
+        error!("Failed to emit {INCIDENT_EVENT}: {error}");
+    }
+}
@@ -32,6 +32,13 @@ export default boot(async ({ app }) => {
   const tauriInvoker = new TauriInvoker(tauri.invoke);
   // make tauriInvoker available as global prop
   app.config.globalProperties.$tauri = tauriInvoker;
+  // the buffered log entries would be lost with the window
+  window.addEventListener('beforeunload', () => {
+    tauriInvoker.flushLogs().catch(console.error);
+  });
+
+  // the crash report of the previous run is offered once the layout is mounted
+  await store.loadCrashReport(tauriInvoker);
 
   // create Config instance and initialize it
   const configDir = await tauri.path.configDir();
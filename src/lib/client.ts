@@ -167,9 +167,10 @@ export class Client {
    * Wrapper for invoking backend method to start local node and connect to the RPC endpoint
    * @param {string} path - folder location
    * @param {string} nodeName - local node name
+   * @param {string | null} keystorePassword - password of the filesystem keystore
    */
-  public async startNode(path: string, nodeName: string): Promise<void> {
-    await this.tauri.startNode(path, nodeName);
+  public async startNode(path: string, nodeName: string, keystorePassword: string | null = null): Promise<void> {
+    await this.tauri.startNode(path, nodeName, keystorePassword);
 
     // TODO: workaround in case node takes some time to fully start, should be replaced with tauri events
     await new Promise((resolve) => setTimeout(resolve, 7000));
@@ -1,2 +1,4 @@
 export const APP_NAME: string = process.env.APP_NAME || 'subspace-desktop';
 export const PLOT_FOLDER = '/plots';
+// start of the `start_node` errors caused by the node database, `NODE_DB_ERROR_PREFIX` in node.rs
+export const NODE_DB_ERROR_PREFIX = 'Node database error';
@@ -3,12 +3,115 @@ import * as tauri from '@tauri-apps/api/tauri';
 import { IConfig } from './config';
 import { getErrorMessage } from './util';
 
+export interface NodeDbReport {
+  exists: boolean;
+  healthy: boolean;
+  error: string | null;
+}
+
+export interface KeystoreKey {
+  keyType: string;
+  public: string;
+}
+
+export interface NodeIdentity {
+  peerId: string;
+  multiaddrs: string[];
+}
+
+export interface NodeThroughput {
+  downloadBytesPerSec: number;
+  uploadBytesPerSec: number;
+  totalDownloadedBytes: number;
+  totalUploadedBytes: number;
+}
+
+export interface Volume {
+  mountPoint: string;
+  fileSystem: string;
+  device: string;
+  freeBytes: number;
+  totalBytes: number;
+  rotational: boolean | null;
+  readOnly: boolean;
+}
+
+export interface PlotDirectoryReport {
+  errors: string[];
+  warnings: string[];
+}
+
+export type LogLevel = 'trace' | 'debug' | 'info' | 'warn' | 'error';
+
+export interface LogQuery {
+  level?: LogLevel;
+  target?: string;
+  from?: string;
+  to?: string;
+  text?: string;
+  // values the fields must have, for example { component: 'dashboard' }
+  fields?: Record<string, unknown>;
+  offset?: number;
+  limit?: number;
+}
+
+export interface LogEntry {
+  time: string;
+  level: LogLevel;
+  target: string;
+  message: string;
+  fields: Record<string, unknown>;
+}
+
+export interface LogPage {
+  entries: LogEntry[];
+  hasMore: boolean;
+}
+
+export interface LogRetentionConfig {
+  maxAgeDays: number;
+  maxTotalSizeMib: number;
+  compress: boolean;
+}
+
+export interface FrontendLogEntry {
+  level: LogLevel;
+  component: string;
+  message: string;
+  fields: Record<string, unknown>;
+}
+
+export interface CrashReport {
+  time: string;
+  appVersion: string;
+  os: string;
+  arch: string;
+  thread: string;
+  message: string;
+  location: string | null;
+  backtrace: string;
+  recentLogs: string[];
+  state: Record<string, unknown>;
+}
+
+export interface ImportedProfile {
+  farmedBlocks: string | null;
+  nodeKey: boolean;
+  farmerIdentity: boolean;
+}
+
+// log entries are sent in batches, at most this often or when the batch is full
+const LOG_FLUSH_INTERVAL_MS = 1000;
+const LOG_BATCH_SIZE = 50;
+
 /**
  * Wrapper for tauri.invoke calls, 
  * responsible for interaction with file system, logging, starting node and farmer, etc.
  */
 class TauriInvoker {
   private invoke: typeof tauri.invoke;
+  private logBuffer: FrontendLogEntry[] = [];
+  private logFlushTimer: ReturnType<typeof setTimeout> | null = null;
 
   /**
    * Create TauriInvoker instance
@@ -105,8 +208,7 @@ class TauriInvoker {
    * @param {unknown} info - data to log
    */
   public async infoLogger(info: unknown): Promise<void> {
-    const message = getErrorMessage(info);
-    return this.invoke('frontend_info_logger', { message });
+    this.log('info', 'app', getErrorMessage(info));
   }
 
   /**
@@ -114,8 +216,39 @@ class TauriInvoker {
    * @param {unknown} error - error to log
    */
   public async errorLogger(error: unknown): Promise<void> {
-    const message = getErrorMessage(error);
-    return this.invoke('frontend_error_logger', { message });
+    this.log('error', 'app', getErrorMessage(error));
+  }
+
+  /**
+   * Structured logging under the `frontend` target, entries are buffered and sent in batches
+   * @param {LogLevel} level - level of the entry, errors are sent right away
+   * @param {string} component - part of the UI the entry comes from, for example `dashboard`
+   * @param {string} message - message of the entry
+   * @param {Record<string, unknown>} fields - key/value context of the entry
+   */
+  public log(level: LogLevel, component: string, message: string, fields: Record<string, unknown> = {}): void {
+    this.logBuffer.push({ level, component, message, fields });
+    if (level === 'error' || this.logBuffer.length >= LOG_BATCH_SIZE) {
+      this.flushLogs().catch(console.error);
+    } else if (this.logFlushTimer === null) {
+      this.logFlushTimer = setTimeout(() => this.flushLogs().catch(console.error), LOG_FLUSH_INTERVAL_MS);
+    }
+  }
+
+  /**
+   * Send the buffered log entries now
+   */
+  public async flushLogs(): Promise<void> {
+    if (this.logFlushTimer !== null) {
+      clearTimeout(this.logFlushTimer);
+      this.logFlushTimer = null;
+    }
+    if (this.logBuffer.length === 0) {
+      return;
+    }
+    const entries = this.logBuffer;
+    this.logBuffer = [];
+    return this.invoke('frontend_log', { entries });
   }
 
   /**
@@ -140,9 +273,228 @@ class TauriInvoker {
    * Start node
    * @param {string} path - base directory
    * @param {string} nodeName - node name (displayed in the app header and Telemetry)
+   * @param {string | null} keystorePassword - password of the filesystem keystore, when it is enabled in the node config
+   */
+  public async startNode(path: string, nodeName: string, keystorePassword: string | null = null): Promise<void> {
+    return this.invoke('start_node', { path, nodeName, keystorePassword });
+  }
+
+  /**
+   * List the keys in the filesystem keystore
+   * @param {string} path - base directory
+   * @param {string | null} keyType - 4 characters key type, all keys when null
+   * @returns {KeystoreKey[]} - key types and hex encoded public keys
+   */
+  public async listKeys(path: string, keyType: string | null = null): Promise<KeystoreKey[]> {
+    return this.invoke('list_keys', { path, keyType });
+  }
+
+  /**
+   * Insert a key into the filesystem keystore, same as the `author_insertKey` RPC method.
+   * Fails if the password is not the one of the keys already in the keystore
+   * @param {string} path - base directory
+   * @param {string} keyType - 4 characters key type
+   * @param {string} suri - secret URI of the key
+   * @param {string} publicKey - hex encoded public key
+   * @param {string | null} password - password of the keystore, the one the node is started with
+   */
+  public async insertKey(
+    path: string,
+    keyType: string,
+    suri: string,
+    publicKey: string,
+    password: string | null = null
+  ): Promise<void> {
+    return this.invoke('insert_key', { path, keyType, suri, public: publicKey, password });
+  }
+
+  /**
+   * Remove a key from the filesystem keystore
+   * @param {string} path - base directory
+   * @param {string} keyType - 4 characters key type
+   * @param {string} publicKey - hex encoded public key
+   */
+  public async removeKey(path: string, keyType: string, publicKey: string): Promise<void> {
+    return this.invoke('remove_key', { path, keyType, public: publicKey });
+  }
+
+  /**
+   * Check the node database for corruption, node must be stopped.
+   * Offered when `startNode` fails with an error starting with 'Node database error'
+   * @param {string} path - base directory
+   * @returns {NodeDbReport} - whether the database exists and is healthy
+   */
+  public async checkNodeDb(path: string): Promise<NodeDbReport> {
+    return this.invoke('check_node_db', { path });
+  }
+
+  /**
+   * Delete the chain database, node key and config are kept, node must be stopped
+   * @param {string} path - base directory
+   */
+  public async purgeChain(path: string): Promise<void> {
+    return this.invoke('purge_chain', { path });
+  }
+
+  /**
+   * Get the network throughput of the node, rates are averaged since the previous call
+   * @returns {NodeThroughput | null} - rates and totals, null if the node is not running
+   */
+  public async getNodeThroughput(): Promise<NodeThroughput | null> {
+    return this.invoke('get_node_throughput');
+  }
+
+  /**
+   * Get the peer ID of the node and the multiaddrs other nodes can use as reserved peer
+   * @param {string} path - base directory
+   * @returns {NodeIdentity | null} - peer ID and multiaddrs, null if the node key is not created yet
+   */
+  public async getNodeIdentity(path: string): Promise<NodeIdentity | null> {
+    return this.invoke('get_node_identity', { path });
+  }
+
+  /**
+   * Replace the node key with a new one, node must be stopped
+   * @param {string} path - base directory
+   * @returns {NodeIdentity} - new peer ID and multiaddrs
+   */
+  public async rotateNodeKey(path: string): Promise<NodeIdentity> {
+    return this.invoke('rotate_node_key', { path });
+  }
+
+  /**
+   * Export the config and optionally the node key, farmer identity and farmed blocks into an archive
+   * @param {string} destination - archive file to create
+   * @param {boolean} includeNodeKey - whether to include the node key
+   * @param {boolean} includeFarmerIdentity - whether to include the farmer identity
+   * @param {string | null} farmedBlocks - farmed blocks history from the local storage
+   */
+  public async exportProfile(
+    destination: string,
+    includeNodeKey: boolean,
+    includeFarmerIdentity: boolean,
+    farmedBlocks: string | null,
+  ): Promise<void> {
+    return this.invoke('export_profile', {
+      destination,
+      includeNodeKey,
+      includeFarmerIdentity,
+      farmedBlocks,
+    });
+  }
+
+  /**
+   * Import a profile archive, node must be stopped
+   * @param {string} source - archive file created by `exportProfile`
+   * @param {string} plotLocation - plot location on this machine
+   * @returns {ImportedProfile} - farmed blocks history to store and what was restored
+   */
+  public async importProfile(source: string, plotLocation: string): Promise<ImportedProfile> {
+    return this.invoke('import_profile', { source, plotLocation });
+  }
+
+  /**
+   * List the mounted volumes a plot could be stored on, only supported on Linux
+   * @returns {Volume[]} - volumes, the ones with the most free space first
+   */
+  public async listVolumes(): Promise<Volume[]> {
+    return this.invoke('list_volumes');
+  }
+
+  /**
+   * Check whether the plot can be stored in the directory
+   * @param {string} path - plot directory, may not exist yet
+   * @returns {PlotDirectoryReport} - blocking errors and warnings
+   */
+  public async checkPlotDirectory(path: string): Promise<PlotDirectoryReport> {
+    return this.invoke('check_plot_directory', { path });
+  }
+
+  /**
+   * Search the log files
+   * @param {LogQuery} query - filters and page, `from` and `to` are ISO timestamps
+   * @returns {LogPage} - matching entries, newest first
+   */
+  public async queryLogs(query: LogQuery): Promise<LogPage> {
+    return this.invoke('query_logs', { query });
+  }
+
+  /**
+   * Start streaming log entries as `log_entries` events, with the entries and the number of dropped ones
+   * @param {LogLevel} level - minimum level of the streamed entries
+   */
+  public async subscribeLogs(level: LogLevel): Promise<void> {
+    return this.invoke('subscribe_logs', { level });
+  }
+
+  /**
+   * Stop streaming log entries
+   */
+  public async unsubscribeLogs(): Promise<void> {
+    return this.invoke('unsubscribe_logs');
+  }
+
+  /**
+   * Get the log filter directives set by the user
+   * @returns {string} - comma separated directives, empty for the defaults
+   */
+  public async getLogFilter(): Promise<string> {
+    return this.invoke('get_log_filter');
+  }
+
+  /**
+   * Change the log filter without restarting, the directives are kept in the config
+   * @param {string} directives - comma separated directives, for example `subspace_farmer=trace`
+   */
+  public async setLogFilter(directives: string): Promise<void> {
+    return this.invoke('set_log_filter', { directives });
+  }
+
+  /**
+   * Get how long and how much of the logs are kept
+   * @returns {LogRetentionConfig}
+   */
+  public async getLogRetentionConfig(): Promise<LogRetentionConfig> {
+    return this.invoke('get_log_retention_config');
+  }
+
+  /**
+   * Change how long and how much of the logs are kept, applied within the hour
+   * @param {LogRetentionConfig} logRetentionConfig - new settings
+   */
+  public async updateLogRetentionConfig(logRetentionConfig: LogRetentionConfig): Promise<void> {
+    return this.invoke('update_log_retention_config', { logRetentionConfig });
+  }
+
+  /**
+   * Write an archive with recent logs, the redacted config, status, disks and system details
+   * @param {string} destination - archive file to create
+   */
+  public async createDiagnosticBundle(destination: string): Promise<void> {
+    return this.invoke('create_diagnostic_bundle', { destination });
+  }
+
+  /**
+   * Get the crash report left by a previous panic, to show to the user on start
+   * @returns {CrashReport | null} - `null` if there is none or it was dismissed
+   */
+  public async getPendingCrashReport(): Promise<CrashReport | null> {
+    return this.invoke('get_pending_crash_report');
+  }
+
+  /**
+   * Save a copy of the pending crash report
+   * @param {string} destination - file to create
+   */
+  public async saveCrashReport(destination: string): Promise<void> {
+    return this.invoke('save_crash_report', { destination });
+  }
+
+  /**
+   * Remove the pending crash report, it is not offered again
    */
-  public async startNode(path: string, nodeName: string): Promise<void> {
-    return this.invoke('start_node', { path, nodeName });
+  public async dismissCrashReport(): Promise<void> {
+    return this.invoke('dismiss_crash_report');
   }
 }
 
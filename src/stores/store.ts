@@ -5,13 +5,17 @@ import { SyncState, FarmedBlock } from '../lib/types';
 import { Client } from '../lib/client';
 import Config from '../lib/config';
 import { IBlockStorage } from '../lib/blockStorage';
-import TauriInvoker from '../lib/tauri';
+import TauriInvoker, { CrashReport } from '../lib/tauri';
+import { NODE_DB_ERROR_PREFIX } from '../lib/constants';
+import { getErrorMessage } from '../lib/util';
 
 export type Status = 'idle' | 'startingNode' | 'syncing' | 'farming';
 
 interface Error {
   title: string;
   message?: string;
+  // the node database could not be opened, the error page offers to check it or purge the chain
+  nodeDb?: boolean;
 }
 
 interface Network {
@@ -46,6 +50,10 @@ interface State {
   plotting: Plotting;
   error: Error;
   hasNewUpdate: boolean;
+  // password of the filesystem keystore, kept in memory only, the node needs it on every start
+  keystorePassword: string | null;
+  // report of the panic that ended the previous run, offered to the user until dismissed
+  crashReport: CrashReport | null;
 }
 
 // constants are also used in unit tests
@@ -93,6 +101,8 @@ export const useStore = defineStore('store', {
       message: '',
     },
     hasNewUpdate: false,
+    keystorePassword: null,
+    crashReport: null,
   }),
 
   getters: {
@@ -171,6 +181,9 @@ export const useStore = defineStore('store', {
     setPlotSize(size: number) {
       this.plotSizeGB = size;
     },
+    setKeystorePassword(password: string | null) {
+      this.keystorePassword = password;
+    },
     /**
      * Set node name value in store and config file.
      * Update store 'error' property in case of failure
@@ -280,7 +293,7 @@ export const useStore = defineStore('store', {
       try {
         if (this.nodeName && this.plotPath) {
           this.setStatus('startingNode');
-          await client.startNode(this.plotPath, this.nodeName);
+          await client.startNode(this.plotPath, this.nodeName, this.keystorePassword);
           // TODO: move back to startFarmer method below after restart workaround is removed (loop is replaced by subscription)
           this.setStatus('syncing');
         } else {
@@ -293,13 +306,22 @@ export const useStore = defineStore('store', {
           });
         }
       } catch (error) {
-        tauri.errorLogger('NODE START | failed to start node');
+        const message = getErrorMessage(error);
+        tauri.errorLogger('NODE START | failed to start node: ' + message);
 
-        this.setError({
-          title: 'errorPage.startNodeFailed',
-          // TODO: replace default error message with specific one
-          message: 'errorPage.defaultErrorMessage',
-        });
+        if (message?.startsWith(NODE_DB_ERROR_PREFIX)) {
+          this.setError({
+            title: 'errorPage.startNodeFailed',
+            message: 'errorPage.nodeDbErrorMessage',
+            nodeDb: true,
+          });
+        } else {
+          this.setError({
+            title: 'errorPage.startNodeFailed',
+            // TODO: replace default error message with specific one
+            message: 'errorPage.defaultErrorMessage',
+          });
+        }
       }
     },
     /**
@@ -378,6 +400,30 @@ export const useStore = defineStore('store', {
     },
     setHasNewUpdate() {
       this.hasNewUpdate = true;
+    },
+    /**
+     * Load the crash report left by the previous run, if there is one
+     * A failure is only logged, the app works without the report
+     * @param {TauriInvoker} tauri - tauri invoker
+     */
+    async loadCrashReport(tauri: TauriInvoker) {
+      try {
+        this.crashReport = await tauri.getPendingCrashReport();
+      } catch (error) {
+        tauri.errorLogger(error);
+      }
+    },
+    /**
+     * Remove the crash report, it is not offered again
+     * @param {TauriInvoker} tauri - tauri invoker
+     */
+    async dismissCrashReport(tauri: TauriInvoker) {
+      try {
+        await tauri.dismissCrashReport();
+        this.crashReport = null;
+      } catch (error) {
+        tauri.errorLogger(error);
+      }
     }
   }
 });
@@ -18,6 +18,7 @@ import {
 } from '../mocks';
 import Config from '../lib/config';
 import { Client } from '../lib/client';
+import TauriInvoker, { CrashReport } from '../lib/tauri';
 
 describe('Store', () => {
   beforeEach(() => {
@@ -186,6 +187,17 @@ describe('Store', () => {
     expect(clientMock.startNode).toHaveBeenCalled();
   });
 
+  it('startNode action should pass the keystore password to the client', async () => {
+    const store = useStore();
+    store.setNodeName(configClassMock, 'random node name');
+    store.setPlotPath('/random-dir');
+    store.setKeystorePassword('random password');
+
+    await store.startNode(clientMock, tauriInvokerMock);
+
+    expect(clientMock.startNode).toHaveBeenCalledWith('/random-dir', 'random node name', 'random password');
+  });
+
   it('startNode action should set error state if client method throws error', async () => {
     const errorMessage = 'random error message';
 
@@ -210,6 +222,30 @@ describe('Store', () => {
     });
   });
 
+  it('startNode action should offer the node database commands if the node database fails', async () => {
+    const errorMessage = 'Node database error: Corrupted database';
+
+    const store = useStore();
+
+    store.setNodeName(configClassMock, 'random node name');
+    store.setPlotPath('/random-dir');
+
+    const client = {
+      ...clientMock,
+      startNode() {
+        return Promise.reject(errorMessage);
+      }
+    } as unknown as Client;
+
+    await store.startNode(client, tauriInvokerMock);
+
+    expect(store.error).toEqual({
+      title: 'errorPage.startNodeFailed',
+      message: 'errorPage.nodeDbErrorMessage',
+      nodeDb: true,
+    });
+  });
+
   it('startNode action should set error state if node name and plot directory are not set before', async () => {
     const errorMessage = 'errorPage.startNodeMissingParams';
 
@@ -384,4 +420,29 @@ describe('Store', () => {
       message: 'errorPage.defaultErrorMessage',
     });
   });
+
+  it('loadCrashReport action should keep the pending crash report', async () => {
+    const report = { message: 'random panic message' } as CrashReport;
+    const invoke = jest.fn().mockResolvedValue(report);
+    const store = useStore();
+    expect(store.crashReport).toBeNull();
+
+    await store.loadCrashReport(new TauriInvoker(invoke));
+
+    expect(invoke).toHaveBeenCalledWith('get_pending_crash_report');
+    expect(store.crashReport).toEqual(report);
+  });
+
+  it('dismissCrashReport action should clear the crash report', async () => {
+    const report = { message: 'random panic message' } as CrashReport;
+    const invoke = jest.fn().mockResolvedValue(report);
+    const store = useStore();
+    await store.loadCrashReport(new TauriInvoker(invoke));
+
+    invoke.mockResolvedValue(undefined);
+    await store.dismissCrashReport(new TauriInvoker(invoke));
+
+    expect(invoke).toHaveBeenLastCalledWith('dismiss_crash_report');
+    expect(store.crashReport).toBeNull();
+  });
 });